@@ -0,0 +1,181 @@
+//! Selects the Rofi version targeted by the bindings.
+//!
+//! The target is taken from, in order of priority:
+//!
+//! 1. the `rofi-*` features or `--cfg rofi_next`;
+//! 2. the `ROFI_PLUGIN_SYS_TARGET` environment variable;
+//! 3. the installed Rofi, found through `pkg-config`, if the `detect` feature is enabled;
+//! 4. the default, Rofi 2.0.
+//!
+//! The result is translated into `cfg`s,
+//! so that the rest of the crate only needs to check one thing.
+
+use std::env;
+#[cfg(feature = "detect")]
+use std::{fs, path::Path};
+
+/// The environment variable that overrides the detected Rofi version.
+const TARGET_VAR: &str = "ROFI_PLUGIN_SYS_TARGET";
+
+#[derive(Clone, Copy)]
+enum Target {
+    Rofi1_7,
+    Rofi2_0,
+    Next,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed={TARGET_VAR}");
+
+    let target = if let Some(target) = explicit_target() {
+        target
+    } else if let Some(value) = env::var_os(TARGET_VAR) {
+        let value = value.to_string_lossy();
+        parse_target(&value).unwrap_or_else(|| {
+            panic!(
+                "invalid value {value:?} for `{TARGET_VAR}`: \
+                 expected `1.7`, `2.0`, `next` or a Rofi version number"
+            )
+        })
+    } else {
+        detect()
+    };
+
+    match target {
+        Target::Rofi1_7 => println!("cargo:rustc-cfg=rofi_1_7"),
+        Target::Rofi2_0 => {}
+        // Emitting the cfg again when it was already set with `--cfg` is harmless.
+        Target::Next => println!("cargo:rustc-cfg=rofi_next"),
+    }
+}
+
+/// The target selected by features or `--cfg`, if any.
+///
+/// When more than one is selected, the crate itself reports the conflict.
+fn explicit_target() -> Option<Target> {
+    if env::var_os("CARGO_FEATURE_ROFI_1_7").is_some() {
+        Some(Target::Rofi1_7)
+    } else if env::var_os("CARGO_FEATURE_ROFI_NEXT").is_some()
+        || env::var_os("CARGO_CFG_ROFI_NEXT").is_some()
+    {
+        Some(Target::Next)
+    } else if env::var_os("CARGO_FEATURE_ROFI_2_0").is_some() {
+        Some(Target::Rofi2_0)
+    } else {
+        None
+    }
+}
+
+/// Parse a target given as `next` or as a Rofi version number like `1.7` or `1.7.5+wayland3`.
+fn parse_target(s: &str) -> Option<Target> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("next") {
+        return Some(Target::Next);
+    }
+    let (major, minor, patch) = parse_version(s)?;
+    Some(target_for_version(major, minor, patch))
+}
+
+/// Parse the leading `major.minor[.patch]` of a version number,
+/// ignoring suffixes such as `-dev` or `+wayland3`.
+fn parse_version(s: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = s.split('.').map(|part| {
+        let digits = part
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(part.len());
+        part[..digits].parse::<u32>().ok()
+    });
+    let major = parts.next()??;
+    let minor = parts.next()??;
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+fn target_for_version(major: u32, minor: u32, patch: u32) -> Target {
+    if (major, minor, patch) < (1, 7, 6) {
+        Target::Rofi1_7
+    } else {
+        Target::Rofi2_0
+    }
+}
+
+#[cfg(feature = "detect")]
+fn target_for_abi(abi_version: u32) -> Option<Target> {
+    match abi_version {
+        6 => Some(Target::Rofi1_7),
+        // Unreleased versions of Rofi share ABI version 7 too,
+        // so `next` can only be selected explicitly.
+        7 => Some(Target::Rofi2_0),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "detect"))]
+fn detect() -> Target {
+    Target::Rofi2_0
+}
+
+#[cfg(feature = "detect")]
+fn detect() -> Target {
+    let library = match pkg_config::Config::new()
+        .cargo_metadata(false)
+        .env_metadata(true)
+        .probe("rofi")
+    {
+        Ok(library) => library,
+        Err(_) => {
+            println!(
+                "cargo:warning=could not find Rofi through pkg-config, \
+                 targeting Rofi 2.0 (set `{TARGET_VAR}` to override)"
+            );
+            return Target::Rofi2_0;
+        }
+    };
+
+    // The ABI version is what actually matters, so prefer it over the package version.
+    for include_path in &library.include_paths {
+        let header = include_path.join("rofi").join("mode-private.h");
+        if let Some(abi_version) = read_abi_version(&header) {
+            if let Some(target) = target_for_abi(abi_version) {
+                return target;
+            }
+            println!(
+                "cargo:warning=Rofi at {} has unsupported plugin ABI version {abi_version}, \
+                 targeting Rofi 2.0 (set `{TARGET_VAR}` to override)",
+                header.display(),
+            );
+            return Target::Rofi2_0;
+        }
+    }
+
+    match parse_version(&library.version) {
+        Some((major, minor, patch)) => target_for_version(major, minor, patch),
+        None => {
+            println!(
+                "cargo:warning=could not parse Rofi version {:?}, \
+                 targeting Rofi 2.0 (set `{TARGET_VAR}` to override)",
+                library.version,
+            );
+            Target::Rofi2_0
+        }
+    }
+}
+
+/// Read the value of `ABI_VERSION` from Rofi's `mode-private.h`,
+/// which is defined as an integer literal such as `7u`.
+#[cfg(feature = "detect")]
+fn read_abi_version(header: &Path) -> Option<u32> {
+    println!("cargo:rerun-if-changed={}", header.display());
+    let contents = fs::read_to_string(header).ok()?;
+    contents.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix('#')?.trim_start();
+        let rest = rest.strip_prefix("define")?.trim_start();
+        let value = rest.strip_prefix("ABI_VERSION")?;
+        if !value.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let value = value.trim().trim_end_matches(['u', 'U', 'l', 'L']);
+        value.parse().ok()
+    })
+}
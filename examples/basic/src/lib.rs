@@ -9,27 +9,16 @@ use ::{
     },
 };
 
-#[no_mangle]
-pub static mut mode: rofi_plugin_sys::Mode = rofi_plugin_sys::Mode {
-    name: "plugin-example-basic\0".as_ptr() as _,
-    cfg_name_key: {
-        let display_name = b"A basic Rofi plugin";
-        let mut buf = [0; 128];
-        let mut i = 0;
-        while i < display_name.len() {
-            buf[i] = display_name[i] as c_char;
-            i += 1;
-        }
-        buf
-    },
-    _init: Some(init),
-    _destroy: Some(destroy),
-    _get_num_entries: Some(get_num_entries),
-    _result: Some(result),
-    _token_match: Some(token_match),
-    _get_display_value: Some(get_display_value),
-    ..rofi_plugin_sys::Mode::default()
-};
+rofi_plugin_sys::export_mode! {
+    name: "plugin-example-basic",
+    display_name: "A basic Rofi plugin",
+    init: init,
+    destroy: destroy,
+    get_num_entries: get_num_entries,
+    result: result,
+    token_match: token_match,
+    get_display_value: get_display_value,
+}
 
 unsafe extern "C" fn init(sw: *mut rofi_plugin_sys::Mode) -> c_int {
     if unsafe { mode_state(sw) }.is_null() {
@@ -65,13 +54,13 @@ unsafe extern "C" fn result(
     _input: *mut *mut c_char,
     _selected_line: c_uint,
 ) -> c_int {
-    if mretv & rofi_plugin_sys::menu::OK != 0
-        || mretv & rofi_plugin_sys::menu::ENTRY_DELETE == rofi_plugin_sys::menu::ENTRY_DELETE
-    {
-        rofi_plugin_sys::RELOAD_DIALOG
-    } else {
-        rofi_plugin_sys::EXIT
-    }
+    let next = match rofi_plugin_sys::MenuAction::from_retv(mretv) {
+        Some(rofi_plugin_sys::MenuAction::Ok { .. } | rofi_plugin_sys::MenuAction::Delete) => {
+            rofi_plugin_sys::ModeMode::RELOAD_DIALOG
+        }
+        _ => rofi_plugin_sys::ModeMode::EXIT,
+    };
+    next.into()
 }
 
 unsafe extern "C" fn get_display_value(
@@ -0,0 +1,4 @@
+fn main() {
+    // Plugins resolve the host functions provided by the mock against the executable.
+    println!("cargo:rustc-link-arg-bins=-Wl,--export-dynamic");
+}
@@ -0,0 +1,13 @@
+#!/bin/sh
+set -eu
+
+# Usage: harness/run.sh <plugin package> [harness options...]
+# e.g. harness/run.sh rofi-plugin-example-basic --query foo --retv ok
+
+package="$1"
+shift
+
+cd "$(dirname "$0")/.."
+# Build the plugin on its own so it doesn't pick up the harness's `mock` feature.
+cargo build -p "$package"
+cargo run -q -p rofi-plugin-harness -- "target/debug/lib$(echo "$package" | tr - _).so" "$@"
@@ -0,0 +1,270 @@
+//! A harness that loads a compiled Rofi plugin and drives its mode on the mock host,
+//! for smoke-testing plugins on machines without Rofi or an X server.
+//!
+//! Run `rofi-plugin-harness --help` for usage,
+//! or `harness/run.sh <plugin package> [options]` to build a plugin in this workspace and run it.
+
+#![warn(unsafe_op_in_unsafe_fn)]
+
+use ::{
+    libloading::Library,
+    rofi_plugin_sys::{
+        self as rofi,
+        matching::{MatchingConfig, MatchingMethod},
+        menu, mock,
+    },
+    std::{
+        env,
+        ffi::{CStr, OsString},
+        os::raw::{c_char, c_int, c_uint},
+        process, ptr,
+    },
+};
+
+const USAGE: &str = "\
+Usage: rofi-plugin-harness [OPTIONS] <PLUGIN>
+
+Loads the Rofi plugin at <PLUGIN>, lists its entries and optionally filters them and fires `_result`.
+
+Options:
+    --query <QUERY>     Filter the entries with the given user input
+    --case-sensitive    Match the query case-sensitively
+    --matching <METHOD> The matching method: normal (default), regex, glob, fuzzy or prefix
+    --retv <FLAGS>      Fire `_result` with the given comma-separated `menu::*` flags
+                        (`ok`, `custom-input`, `entry-delete`, `custom-action`, ...),
+                        `kb-custom-<N>` for a custom key binding, or integers
+    --select <LINE>     The selected line passed to `_result` (default: the first match)
+    --input <INPUT>     The user input passed to `_result` (default: the query)
+    --arg <ARG>         Add a command-line argument visible to the plugin (may be repeated)
+    --option <KEY=VAL>  Set an option registered by the plugin (may be repeated)
+    -h, --help          Print this help
+";
+
+#[derive(Default)]
+struct Options {
+    plugin: Option<OsString>,
+    query: Option<String>,
+    case_sensitive: bool,
+    matching: MatchingMethod,
+    retv: Option<c_int>,
+    select: Option<c_uint>,
+    input: Option<String>,
+    args: Vec<String>,
+    options: Vec<(String, String)>,
+}
+
+fn main() {
+    let options = match parse_options(env::args_os().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+    if let Err(e) = run(options) {
+        eprintln!("error: {e}");
+        process::exit(1);
+    }
+}
+
+fn parse_options(mut args: impl Iterator<Item = OsString>) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| -> Result<String, String> {
+            args.next()
+                .ok_or_else(|| format!("missing value for `{name}`"))?
+                .into_string()
+                .map_err(|_| format!("value for `{name}` is not valid UTF-8"))
+        };
+        match arg.to_str() {
+            Some("-h" | "--help") => {
+                print!("{USAGE}");
+                process::exit(0);
+            }
+            Some("--query") => options.query = Some(value("--query")?),
+            Some("--case-sensitive") => options.case_sensitive = true,
+            Some("--matching") => {
+                let method = value("--matching")?;
+                options.matching = MatchingMethod::from_name(&method)
+                    .ok_or_else(|| format!("unknown matching method `{method}`"))?;
+            }
+            Some("--retv") => options.retv = Some(parse_retv(&value("--retv")?)?),
+            Some("--select") => {
+                let line = value("--select")?;
+                let line = line.parse().map_err(|_| format!("invalid line `{line}`"))?;
+                options.select = Some(line);
+            }
+            Some("--input") => options.input = Some(value("--input")?),
+            Some("--arg") => options.args.push(value("--arg")?),
+            Some("--option") => {
+                let option = value("--option")?;
+                let (key, value) = option
+                    .split_once('=')
+                    .ok_or_else(|| format!("invalid option `{option}`: expected `KEY=VALUE`"))?;
+                options.options.push((key.to_owned(), value.to_owned()));
+            }
+            Some(other) if other.starts_with('-') => {
+                return Err(format!("unknown option `{other}`"))
+            }
+            _ if options.plugin.is_none() => options.plugin = Some(arg),
+            _ => return Err("more than one plugin given".to_owned()),
+        }
+    }
+    if options.plugin.is_none() {
+        return Err("no plugin given".to_owned());
+    }
+    Ok(options)
+}
+
+fn parse_retv(s: &str) -> Result<c_int, String> {
+    s.split(',').try_fold(0, |retv, flag| {
+        let flag = flag.trim();
+        let value = match flag {
+            "ok" => menu::OK,
+            "cancel" => menu::CANCEL,
+            "next" => menu::NEXT,
+            "custom-input" => menu::CUSTOM_INPUT,
+            "entry-delete" => menu::ENTRY_DELETE,
+            "quick-switch" => menu::QUICK_SWITCH,
+            "custom-command" => menu::CUSTOM_COMMAND,
+            "previous" => menu::PREVIOUS,
+            "complete" => menu::COMPLETE,
+            "custom-action" => menu::CUSTOM_ACTION,
+            _ if flag.starts_with("kb-custom-") => {
+                let n = &flag["kb-custom-".len()..];
+                match n.parse() {
+                    Ok(n) if (1..=rofi::MenuAction::MAX_CUSTOM_ACTION).contains(&n) => {
+                        rofi::MenuAction::CustomAction(n).to_retv()
+                    }
+                    _ => return Err(format!("invalid custom key binding `{flag}`")),
+                }
+            }
+            _ => match flag.strip_prefix("0x") {
+                Some(hex) => c_int::from_str_radix(hex, 16),
+                None => flag.parse(),
+            }
+            .map_err(|_| format!("unknown menu flag `{flag}`"))?,
+        };
+        Ok(retv | value)
+    })
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let path = options.plugin.unwrap();
+    let library = unsafe { Library::new(&path) }
+        .map_err(|e| format!("failed to load {}: {e}", path.to_string_lossy()))?;
+    let mode = unsafe { library.get::<*mut rofi::Mode>(b"mode\0") }
+        .map_err(|e| format!("plugin does not export a `mode` symbol: {e}"))?;
+    let mode: *mut rofi::Mode = *mode;
+
+    // GModule calls this when Rofi loads the plugin; it is where options are registered.
+    if let Ok(check_init) = unsafe {
+        library.get::<unsafe extern "C" fn(*mut rofi::GModule) -> *const c_char>(
+            b"g_module_check_init\0",
+        )
+    } {
+        let error = unsafe { check_init(ptr::null_mut()) };
+        if !error.is_null() {
+            return Err(format!("plugin failed to load: {}", unsafe {
+                lossy(error)
+            }));
+        }
+    }
+    for (key, value) in &options.options {
+        if !mock::set_option(key, value) {
+            return Err(format!(
+                "plugin has no option `{key}` or `{value}` is invalid for it"
+            ));
+        }
+    }
+
+    let abi_version = unsafe { (*mode).abi_version };
+    if abi_version != rofi::ABI_VERSION {
+        return Err(format!(
+            "plugin has ABI version {abi_version}, expected {}",
+            rofi::ABI_VERSION,
+        ));
+    }
+
+    mock::set_arguments(options.args);
+    mock::set_matching(MatchingConfig {
+        method: options.matching,
+        ..MatchingConfig::default()
+    });
+
+    let mut driver = unsafe { mock::Driver::new(mode) };
+    let name = unsafe { lossy(rofi::mode_get_name(mode)) };
+    println!("loaded mode `{name}` (ABI version {abi_version})");
+    if !driver.init() {
+        return Err("mode failed to initialize".to_owned());
+    }
+    println!("display name: {}", driver.display_name());
+    if let Some(message) = driver.message() {
+        println!("message: {message}");
+    }
+
+    let num_entries = driver.num_entries();
+    println!("{num_entries} entries:");
+    for line in 0..num_entries {
+        print_entry(&driver, line);
+    }
+
+    let mut selected = options.select;
+    if let Some(query) = &options.query {
+        let matches = driver.filter(query, options.case_sensitive);
+        println!("{} entries match {query:?}:", matches.len());
+        for &line in &matches {
+            print_entry(&driver, line);
+        }
+        selected = selected.or_else(|| matches.first().copied());
+    }
+
+    if let Some(retv) = options.retv {
+        let line = selected.unwrap_or(0);
+        let input = options.input.or(options.query).unwrap_or_default();
+        let (next, input) = driver.result(retv, &input, line);
+        let action = match rofi::MenuAction::from_retv(retv) {
+            Some(action) => format!("{action:?}"),
+            None => "unknown action".to_owned(),
+        };
+        println!(
+            "result({retv:#010x} = {action}, line {line}) returned {}",
+            describe_mode(next)
+        );
+        println!("input after result: {input:?}");
+    }
+
+    for call in mock::take_calls() {
+        println!("host call: {call:?}");
+    }
+
+    driver.destroy();
+    drop(library);
+    Ok(())
+}
+
+fn print_entry(driver: &mock::Driver, line: c_uint) {
+    let value = driver.display_value(line);
+    print!("  {line:>4}  {}", value.text);
+    if !value.state.is_empty() {
+        print!("  [{:?}]", value.state);
+    }
+    if !value.attributes.is_empty() {
+        print!("  [{} attributes]", value.attributes.len());
+    }
+    println!();
+}
+
+fn describe_mode(mode: c_int) -> String {
+    match rofi::ModeMode::new(mode) {
+        Some(mode) => format!("{mode:?}"),
+        None => format!("invalid value {mode}"),
+    }
+}
+
+unsafe fn lossy(s: *const c_char) -> String {
+    if s.is_null() {
+        return String::new();
+    }
+    unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned()
+}
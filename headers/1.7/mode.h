@@ -0,0 +1,94 @@
+/*
+ * Excerpt of Rofi's mode.h,
+ * containing the declarations bound by rofi-plugin-sys.
+ *
+ * rofi
+ *
+ * MIT/X11 License
+ * Copyright © 2013-2022 Qball Cow <qball@gmpclient.org>
+ */
+
+#ifndef ROFI_MODE_H
+#define ROFI_MODE_H
+#include "rofi-types.h"
+#include <cairo.h>
+#include <gmodule.h>
+G_BEGIN_DECLS
+
+/**
+ * Type of a mode.
+ * Access should be done via mode_* functions.
+ */
+typedef struct rofi_mode Mode;
+
+/**
+ * Enum used to sum the possible states of ROFI.
+ */
+typedef enum {
+  /** Exit. */
+  MODE_EXIT = 1000,
+  /** Skip to the next cycle-able dialog. */
+  NEXT_DIALOG = 1001,
+  /** Reload current DIALOG */
+  RELOAD_DIALOG = 1002,
+  /** Previous dialog */
+  PREVIOUS_DIALOG = 1003,
+  /** Reloads the dialog and unset user input */
+  RESET_DIALOG = 1004,
+} ModeMode;
+
+/**
+ * State returned by the rofi window.
+ */
+typedef enum {
+  /** Entry is selected. */
+  MENU_OK = 0x00010000,
+  /** User canceled the operation. (e.g. pressed escape) */
+  MENU_CANCEL = 0x00020000,
+  /** User requested a mode switch */
+  MENU_NEXT = 0x00040000,
+  /** Custom (non-matched) input was entered. */
+  MENU_CUSTOM_INPUT = 0x00080000,
+  /** User wanted to delete entry from history. */
+  MENU_ENTRY_DELETE = 0x00100000,
+  /** User wants to jump to another switcher. */
+  MENU_QUICK_SWITCH = 0x00200000,
+  /** User wants to jump to custom command. */
+  MENU_CUSTOM_COMMAND = 0x00800000,
+  /** Go to the previous menu. */
+  MENU_PREVIOUS = 0x00400000,
+  /** Go to the complete. */
+  MENU_COMPLETE = 0x01000000,
+  /** Bindings specifics */
+  MENU_CUSTOM_ACTION = 0x10000000,
+  /** Mask */
+  MENU_LOWER_MASK = 0x0000FFFF
+} MenuReturn;
+
+int mode_init(Mode *mode);
+void mode_destroy(Mode *mode);
+unsigned int mode_get_num_entries(const Mode *mode);
+char *mode_get_display_value(const Mode *mode, unsigned int selected_line,
+                             int *state, GList **attribute_list,
+                             int get_entry);
+cairo_surface_t *mode_get_icon(Mode *mode, unsigned int selected_line,
+                               unsigned int height);
+char *mode_get_completion(const Mode *mode, unsigned int selected_line);
+ModeMode mode_result(Mode *mode, int menu_retv, char **input,
+                     unsigned int selected_line);
+int mode_token_match(const Mode *mode, rofi_int_matcher **tokens,
+                     unsigned int selected_line);
+const char *mode_get_name(const Mode *mode);
+void mode_free(Mode **mode);
+void *mode_get_private_data(const Mode *mode);
+void mode_set_private_data(Mode *mode, void *pd);
+const char *mode_get_display_name(const Mode *mode);
+void mode_set_config(Mode *mode);
+char *mode_preprocess_input(Mode *mode, const char *input);
+char *mode_get_message(const Mode *mode);
+int mode_get_abi_version(Mode *const mode);
+void mode_plugin_set_module(Mode *mode, GModule *mod);
+GModule *mode_plugin_get_module(Mode *mode);
+
+G_END_DECLS
+#endif
@@ -0,0 +1,28 @@
+/*
+ * Excerpt of Rofi's rofi-icon-fetcher.h,
+ * containing the declarations bound by rofi-plugin-sys.
+ *
+ * rofi
+ *
+ * MIT/X11 License
+ * Copyright © 2013-2022 Qball Cow <qball@gmpclient.org>
+ */
+
+#ifndef ROFI_ICON_FETCHER_H
+#define ROFI_ICON_FETCHER_H
+
+#include <cairo.h>
+#include <glib.h>
+#include <stdint.h>
+G_BEGIN_DECLS
+
+void rofi_icon_fetcher_init(void);
+void rofi_icon_fetcher_destroy(void);
+uint32_t rofi_icon_fetcher_query(const char *name, const int size);
+uint32_t rofi_icon_fetcher_query_advanced(const char *name, const int wsize,
+                                          const int hsize);
+cairo_surface_t *rofi_icon_fetcher_get(const uint32_t uid);
+gboolean rofi_icon_fetcher_file_is_image(const char *const path);
+
+G_END_DECLS
+#endif // ROFI_ICON_FETCHER_H
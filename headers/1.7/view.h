@@ -0,0 +1,20 @@
+/*
+ * Excerpt of Rofi's view.h,
+ * containing the declarations bound by rofi-plugin-sys.
+ *
+ * rofi
+ *
+ * MIT/X11 License
+ * Copyright © 2013-2022 Qball Cow <qball@gmpclient.org>
+ */
+
+#ifndef ROFI_VIEW_H
+#define ROFI_VIEW_H
+
+#include <glib.h>
+G_BEGIN_DECLS
+
+void rofi_view_reload(void);
+
+G_END_DECLS
+#endif
@@ -0,0 +1,43 @@
+/*
+ * Excerpt of Rofi's xrmoptions.h,
+ * containing the declarations bound by rofi-plugin-sys.
+ *
+ * rofi
+ *
+ * MIT/X11 License
+ * Copyright © 2013-2023 Qball Cow <qball@gmpclient.org>
+ */
+
+#ifndef ROFI_XRMOPTIONS_H
+#define ROFI_XRMOPTIONS_H
+
+#include <glib.h>
+
+/**
+ * Type of the config options.
+ */
+typedef enum {
+  /** Config option is string */
+  xrm_String = 0,
+  /** Config option is an unsigned number */
+  xrm_Number = 1,
+  /** Config option is a signed number */
+  xrm_SNumber = 2,
+  /** Config option is a boolean (true/false) value*/
+  xrm_Boolean = 3,
+  /** Config option is a character */
+  xrm_Char = 4
+} XrmOptionType;
+
+/**
+ * @param type The type of the value
+ * @param key  The key refering to this configuration option
+ * @param value The value to update based [out][in]
+ * @param comment Description of this configuration option
+ *
+ * Add a config option to the list of available options.
+ */
+void config_parser_add_option(XrmOptionType type, const char *key,
+                              void **value, const char *comment);
+
+#endif
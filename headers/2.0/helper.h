@@ -0,0 +1,87 @@
+/*
+ * Excerpt of Rofi's helper.h,
+ * containing the declarations bound by rofi-plugin-sys.
+ *
+ * rofi
+ *
+ * MIT/X11 License
+ * Copyright © 2013-2023 Qball Cow <qball@gmpclient.org>
+ */
+
+#ifndef ROFI_HELPER_H
+#define ROFI_HELPER_H
+
+#include "rofi-types.h"
+#include <cairo.h>
+G_BEGIN_DECLS
+
+/**
+ * The startup notification context of the application to launch
+ */
+typedef struct {
+  /** The name of the application */
+  const gchar *name;
+  /** The binary name of the application */
+  const gchar *binary;
+  /** The description of the launch */
+  const gchar *description;
+  /** The icon name of the application */
+  const gchar *icon;
+  /** The application id (desktop file with the .desktop suffix) */
+  const gchar *app_id;
+  /** The window manager class of the application */
+  const gchar *wmclass;
+  /** The command we run */
+  const gchar *command;
+} RofiHelperExecuteContext;
+
+int helper_parse_setup(char *string, char ***output, int *length, ...);
+rofi_int_matcher **helper_tokenize(const char *input, int case_sensitive);
+void helper_tokenize_free(rofi_int_matcher **tokens);
+int find_arg_char(const char *const key, char *val);
+int find_arg_uint(const char *const key, unsigned int *val);
+int find_arg_int(const char *const key, int *val);
+int find_arg_str(const char *const key, char **val);
+const char **find_arg_strv(const char *const key);
+int find_arg(const char *const key);
+int helper_token_match(rofi_int_matcher *const *tokens, const char *input);
+int execute_generator(const char *cmd);
+int create_pid_file(const char *pidfile, gboolean kill);
+void remove_pid_file(int fd);
+int config_sanity_check(void);
+char helper_parse_char(const char *arg);
+void cmd_set_arguments(int argc, char **argv);
+char *rofi_expand_path(const char *input);
+unsigned int levenshtein(const char *needle, const glong needlelen,
+                         const char *haystack, const glong haystacklen,
+                         int case_sensitive);
+char *rofi_force_utf8(const gchar *data, ssize_t length);
+char *rofi_latin_to_utf8_strdup(const char *input, gssize length);
+int rofi_scorer_fuzzy_evaluate(const char *pattern, glong plen, const char *str,
+                               glong slen, int case_sensitive);
+int utf8_strncmp(const char *a, const char *b, size_t n);
+gboolean helper_execute(const char *wd, char **args, const char *error_precmd,
+                        const char *error_cmd,
+                        RofiHelperExecuteContext *context);
+gboolean helper_execute_command(const char *wd, const char *cmd,
+                                gboolean run_in_term,
+                                RofiHelperExecuteContext *context);
+cairo_surface_t *cairo_image_surface_create_from_svg(const gchar *file,
+                                                     int height);
+void parse_ranges(char *input, rofi_range_pair **list, unsigned int *length);
+int parse_case_sensitivity(const char *input);
+void rofi_output_formatted_line(const char *format, const char *string,
+                                int selected_line, const char *filter);
+char *helper_string_replace_if_exists(char *string, ...);
+char *helper_get_theme_path(const char *file, const char **ext,
+                            const char *parent_dir);
+ConfigEntry *rofi_config_find_widget(const char *name, const char *state,
+                                     gboolean exact);
+Property *rofi_theme_find_property(ConfigEntry *widget, PropertyType type,
+                                   const char *property, gboolean exact);
+const char *helper_get_matching_mode_str(void);
+void helper_select_next_matching_mode(void);
+void helper_select_previous_matching_mode(void);
+
+G_END_DECLS
+#endif // ROFI_HELPER_H
@@ -0,0 +1,184 @@
+/*
+ * Excerpt of Rofi's settings.h,
+ * containing the declarations bound by rofi-plugin-sys.
+ *
+ * rofi
+ *
+ * MIT/X11 License
+ * Copyright © 2013-2023 Qball Cow <qball@gmpclient.org>
+ */
+
+#ifndef ROFI_SETTINGS_H
+#define ROFI_SETTINGS_H
+
+#include "rofi-types.h"
+#include <glib.h>
+
+/**
+ * Possible methods of matching.
+ */
+typedef enum {
+  MM_NORMAL = 0,
+  MM_REGEX = 1,
+  MM_GLOB = 2,
+  MM_FUZZY = 3,
+  MM_PREFIX = 4
+} MatchingMethod;
+
+/**
+ * Possible sorting methods for listview.
+ */
+typedef enum { SORT_NORMAL = 0, SORT_FZF = 1 } SortingMethod;
+
+/**
+ * Settings structure holding all (static) configurable options.
+ * @ingroup CONFIGURATION
+ */
+typedef struct {
+  /** List of enabled modes */
+  char *modes;
+  /** Font string (pango format) */
+  char *menu_font;
+
+  /** Whether to load and show icons  */
+  gboolean show_icons;
+
+  /** Terminal to use  */
+  char *terminal_emulator;
+  /** SSH client to use */
+  char *ssh_client;
+  /** Command to execute when ssh session is selected */
+  char *ssh_command;
+  /** Command for executing an application */
+  char *run_command;
+  /** Command for executing an application in a terminal */
+  char *run_shell_command;
+  /** Command for listing executables */
+  char *run_list_command;
+  /** Command for window */
+  char *window_command;
+  /** Window fields to match in window mode */
+  char *window_match_fields;
+  /** Theme for icons */
+  char *icon_theme;
+
+  /** Windows location/gravity */
+  WindowLocation location;
+  /** Y offset */
+  int y_offset;
+  /** X offset */
+  int x_offset;
+  /** Always show config.menu_lines lines, even if less lines are available */
+  unsigned int fixed_num_lines;
+  /** Do not use history */
+  unsigned int disable_history;
+  /** Programs ignored for history */
+  char *ignored_prefixes;
+  /** Toggle to enable sorting. */
+  unsigned int sort;
+  /** Sorting method. */
+  SortingMethod sorting_method_enum;
+  /** Sorting method. */
+  char *sorting_method;
+  /** Desktop entries to match in drun */
+  char *drun_match_fields;
+  /** Only show entries in this category */
+  char *drun_categories;
+  /** Desktop entry show actions */
+  unsigned int drun_show_actions;
+  /** Desktop format display */
+  char *drun_display_format;
+  /** Desktop Link launch command */
+  char *drun_url_launcher;
+  /** Search case sensitivity */
+  unsigned int case_sensitive;
+  /** Smart case sensitivity like vim */
+  unsigned int case_smart;
+  /** Cycle through in the element list */
+  unsigned int cycle;
+  /** Height of an element in number of rows */
+  int element_height;
+  /** Sidebar mode, show the modes */
+  unsigned int sidebar_mode;
+  /** Mouse hover automatically selects */
+  gboolean hover_select;
+  /** Auto select. */
+  unsigned int auto_select;
+  /** Hosts file parsing */
+  unsigned int parse_hosts;
+  /** Knonw_hosts file parsing */
+  unsigned int parse_known_hosts;
+  /** Modes to combine into one view. */
+  char *combi_modes;
+  /** Matching method */
+  char *matching;
+  /** Matching method */
+  MatchingMethod matching_method;
+  /** Tokenize the input */
+  unsigned int tokenize;
+  /** Monitors */
+  char *monitor;
+  /** filter */
+  char *filter;
+  /** dpi */
+  int dpi;
+  /** Number threads (1 to disable) */
+  unsigned int threads;
+  /** Scroll method */
+  unsigned int scroll_method;
+
+  /** Window format */
+  char *window_format;
+  /** Click outside the window to exit */
+  int click_to_exit;
+
+  /** Theme file */
+  char *theme;
+  /** Path where plugins can be found. */
+  char *plugin_path;
+
+  /** Maximum history length per mode. */
+  unsigned int max_history_size;
+  /** Hide the mode prefix in combi mode */
+  gboolean combi_hide_mode_prefix;
+  /** Format of the mode prefix in combi mode */
+  char *combi_display_format;
+
+  /** Character that negates a token */
+  char matching_negate_char;
+
+  /** Cache directory. */
+  char *cache_dir;
+
+  /** Window Thumbnails */
+  gboolean window_thumbnail;
+
+  /** drun cache */
+  gboolean drun_use_desktop_cache;
+  /** Rebuild the drun cache */
+  gboolean drun_reload_desktop_cache;
+
+  /** Benchmark */
+  gboolean benchmark_ui;
+
+  /** Normalize the string before matching */
+  gboolean normalize_match;
+  /** Steal focus */
+  gboolean steal_focus;
+  /** fallback icon */
+  char *application_fallback_icon;
+
+  /** refilter timeout ms */
+  unsigned int refilter_timeout_limit;
+
+  /** workaround for broken xserver (#300 on xserver, #611) */
+  gboolean xserver_i300_workaround;
+  /** What mode to use for completion */
+  char *completer_mode;
+} Settings;
+
+/** Global instance of the configuration structure. */
+extern Settings config;
+
+#endif
+
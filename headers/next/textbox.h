@@ -0,0 +1,42 @@
+/*
+ * Excerpt of Rofi's textbox.h,
+ * containing the declarations bound by rofi-plugin-sys.
+ *
+ * rofi
+ *
+ * MIT/X11 License
+ * Copyright © 2013-2023 Qball Cow <qball@gmpclient.org>
+ */
+
+#ifndef ROFI_TEXTBOX_H
+#define ROFI_TEXTBOX_H
+
+#include <glib.h>
+G_BEGIN_DECLS
+
+/**
+ * Flags to specify the state of the textbox.
+ */
+typedef enum {
+  /** Normal */
+  NORMAL = 0,
+  /** Text in box is urgent. */
+  URGENT = 1,
+  /** Text in box is active. */
+  ACTIVE = 2,
+  /** Text in box is selected. */
+  SELECTED = 4,
+  /** Text in box has pango markup. */
+  MARKUP = 8,
+  /** Text is on an alternate row */
+  ALT = 16,
+  /** Render font highlighted (inverse colors). */
+  HIGHLIGHT = 32,
+  /** Mask for alternate and highlighted */
+  FMOD_MASK = (ALT | HIGHLIGHT),
+  /** Mask of bits indicating state */
+  STATEMASK = ~(SELECTED | MARKUP | ALT | HIGHLIGHT)
+} TextBoxFontType;
+
+G_END_DECLS
+#endif
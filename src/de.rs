@@ -0,0 +1,416 @@
+//! Deserialize plugin configuration sections from `config.rasi` with Serde.
+//!
+//! **Only available with the `serde` feature.**
+//!
+//! A [`ThemeWidget`] (or [`ConfigEntry`]) holds its properties in a hash table of [`Property`]s,
+//! whose values are a union tagged by [`PropertyType`].
+//! [`from_widget`] walks that table and deserializes it like a map,
+//! converting each property according to its type:
+//!
+//! | Property type | Deserialized as |
+//! |---|---|
+//! | integer | `i32` |
+//! | double | `f64` |
+//! | string | string |
+//! | character | `char` |
+//! | boolean | `bool` |
+//! | list | sequence |
+//! | color | [`ThemeColor`] |
+//! | distance or padding | [`Distance`] or [`Padding`] |
+//! | position | [`WindowLocation`](crate::WindowLocation) bits, as `u32` |
+//! | orientation | `"vertical"` or `"horizontal"` |
+//! | cursor | `"default"`, `"pointer"` or `"text"` |
+//! | link | the linked property |
+//!
+//! Other property types are an error if the target type tries to read them.
+//! Nested sections are deserialized as nested maps.
+//! Rofi's property names use dashes,
+//! so structs usually need `#[serde(rename_all = "kebab-case")]`.
+//!
+//! For example, with this in `config.rasi`:
+//!
+//! ```text
+//! configuration {
+//!     my-plugin {
+//!         notes-dir: "~/notes";
+//!         max-results: 20;
+//!         extensions: [ "md", "txt" ];
+//!         highlight: #ff8800;
+//!         spacing: 4px;
+//!     }
+//! }
+//! ```
+//!
+//! the section can be read with:
+//!
+//! ```no_run
+//! use {
+//!     rofi_plugin_sys::{de, ThemeColor},
+//!     serde::Deserialize,
+//! };
+//!
+//! #[derive(Deserialize, Default)]
+//! #[serde(default, rename_all = "kebab-case")]
+//! struct Config {
+//!     notes_dir: Option<String>,
+//!     max_results: i32,
+//!     show_preview: bool,
+//!     extensions: Vec<String>,
+//!     highlight: Option<ThemeColor>,
+//!     spacing: Option<de::Distance>,
+//! }
+//!
+//! let config: Config = de::from_config("my-plugin")?;
+//! # Ok::<(), de::Error>(())
+//! ```
+
+use {
+    crate::{
+        distance::{self, DistanceContext},
+        Property, PropertyRef, RofiCursorType, RofiDistance, RofiDistanceModifier, RofiLineStyle,
+        RofiOrientation, RofiPixelUnit, ThemeColor, ThemeWidget,
+    },
+    ::serde::{
+        de::{
+            self,
+            value::{MapDeserializer, SeqDeserializer},
+            DeserializeOwned, IgnoredAny, IntoDeserializer, MapAccess, Visitor,
+        },
+        forward_to_deserialize_any, Deserialize, Deserializer,
+    },
+    ::std::{
+        ffi::CStr,
+        fmt::{self, Display, Formatter},
+        mem,
+        os::raw::{c_char, c_int},
+        ptr,
+    },
+};
+
+#[cfg(doc)]
+use crate::ConfigEntry;
+
+/// An error that occurred while deserializing a configuration section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl ::std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self {
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Deserialize the section called `name` in the `configuration` block of the user's config,
+/// found with [`helper::config_find_widget`](crate::helper::config_find_widget).
+///
+/// If the section does not exist, `T` is deserialized from an empty map,
+/// so fields with `#[serde(default)]` get their default values.
+///
+/// Like the other functions provided by Rofi,
+/// this should be called from Rofi's main thread.
+///
+/// **Not available with the `rofi-1-7` feature.**
+#[cfg(not(rofi_1_7))]
+pub fn from_config<T: DeserializeOwned>(name: &str) -> Result<T, Error> {
+    let name = ::std::ffi::CString::new(name)
+        .map_err(|_| de::Error::custom("section name contains a NUL byte"))?;
+    let widget = unsafe { crate::helper::config_find_widget(name.as_ptr(), ptr::null(), 1) };
+    if widget.is_null() {
+        return T::deserialize(Value::Map(Vec::new()));
+    }
+    unsafe { from_widget(widget) }
+}
+
+/// Deserialize the properties and child widgets of `widget`.
+///
+/// # Safety
+///
+/// `widget` must point to a valid [`ThemeWidget`] as created by Rofi's theme parser,
+/// which is not modified during the call.
+pub unsafe fn from_widget<T: DeserializeOwned>(widget: *const ThemeWidget) -> Result<T, Error> {
+    T::deserialize(unsafe { widget_value(widget) })
+}
+
+/// A distance, such as `4px` or `10%`.
+///
+/// This can also be deserialized from a padding whose sides are all the same,
+/// which is how Rofi stores single distances.
+/// Distances computed with `calc()` are not supported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Distance {
+    /// The value of the distance, in `unit`s.
+    pub value: f64,
+    /// The unit of the distance.
+    pub unit: RofiPixelUnit,
+    /// The line style, for borders.
+    pub style: RofiLineStyle,
+}
+
+impl<'de> Deserialize<'de> for Distance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DistanceVisitor;
+
+        impl<'de> Visitor<'de> for DistanceVisitor {
+            type Value = Distance;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("a distance")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Distance, A::Error> {
+                let (mut value, mut unit, mut style) = (None, None, None);
+                let mut sides = Vec::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    match &*key {
+                        "value" => value = Some(map.next_value()?),
+                        "unit" => unit = Some(map.next_value()?),
+                        "style" => style = Some(map.next_value()?),
+                        "top" | "right" | "bottom" | "left" => sides.push(map.next_value()?),
+                        _ => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+                if let Some((&first, rest)) = sides.split_first() {
+                    if rest.iter().any(|&side| side != first) {
+                        return Err(de::Error::custom(
+                            "expected a single distance, found a padding with different sides",
+                        ));
+                    }
+                    return Ok(first);
+                }
+                Ok(Distance {
+                    value: value.ok_or_else(|| de::Error::missing_field("value"))?,
+                    unit: unit.unwrap_or(RofiPixelUnit::Px),
+                    style: style.unwrap_or(RofiLineStyle::Solid),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(DistanceVisitor)
+    }
+}
+
+impl Distance {
+    /// Convert the distance to pixels, like Rofi does.
+    ///
+    /// See [`distance::unit_pixels`] for the meaning of `orientation`.
+    pub fn to_pixels(&self, orientation: RofiOrientation, context: &DistanceContext) -> c_int {
+        distance::unit_pixels(self.value, self.unit, orientation, context)
+    }
+}
+
+/// A padding, such as `4px 8px`, with a [`Distance`] for each side.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Padding {
+    /// The distance at the top.
+    pub top: Distance,
+    /// The distance on the right.
+    pub right: Distance,
+    /// The distance at the bottom.
+    pub bottom: Distance,
+    /// The distance on the left.
+    pub left: Distance,
+}
+
+/// An owned copy of a property value, ready to be deserialized.
+enum Value {
+    Int(i32),
+    UInt(u32),
+    Double(f64),
+    String(String),
+    Char(char),
+    Bool(bool),
+    List(Vec<Value>),
+    Map(Vec<(String, Value)>),
+    /// A value that cannot be deserialized, with the reason why.
+    Invalid(String),
+}
+
+unsafe fn string(s: *const c_char) -> String {
+    if s.is_null() {
+        return String::new();
+    }
+    unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned()
+}
+
+unsafe fn widget_value(widget: *const ThemeWidget) -> Value {
+    let widget = unsafe { &*widget };
+    let mut entries = Vec::new();
+
+    if !widget.properties.is_null() {
+        let mut iter = unsafe { mem::zeroed::<glib_sys::GHashTableIter>() };
+        unsafe { glib_sys::g_hash_table_iter_init(&mut iter, widget.properties) };
+        let (mut key, mut value) = (ptr::null_mut(), ptr::null_mut());
+        while unsafe { glib_sys::g_hash_table_iter_next(&mut iter, &mut key, &mut value) } != 0 {
+            let property = value.cast::<Property>();
+            entries.push((unsafe { string(key.cast()) }, unsafe {
+                property_value(property)
+            }));
+        }
+    }
+
+    for i in 0..widget.num_widgets as usize {
+        let child = unsafe { *widget.widgets.add(i) };
+        entries.push((unsafe { string((*child).name) }, unsafe {
+            widget_value(child)
+        }));
+    }
+
+    Value::Map(entries)
+}
+
+unsafe fn property_value(property: *const Property) -> Value {
+    let property = match unsafe { property.as_ref() } {
+        Some(property) => property,
+        None => return Value::Invalid("null property".to_owned()),
+    };
+    match unsafe { PropertyRef::new(property) } {
+        PropertyRef::Integer(i) => Value::Int(i),
+        PropertyRef::Double(f) => Value::Double(f),
+        PropertyRef::String(s) => Value::String(s.to_string_lossy().into_owned()),
+        PropertyRef::Char(c) => Value::Char(char::from(c as u8)),
+        PropertyRef::Boolean(b) => Value::Bool(b),
+        PropertyRef::Color(color) => color_value(color),
+        PropertyRef::Padding(padding) => Value::Map(vec![
+            ("top".to_owned(), distance_value(padding.top)),
+            ("right".to_owned(), distance_value(padding.right)),
+            ("bottom".to_owned(), distance_value(padding.bottom)),
+            ("left".to_owned(), distance_value(padding.left)),
+        ]),
+        PropertyRef::List(items) => {
+            Value::List(items.map(|item| unsafe { property_value(item) }).collect())
+        }
+        PropertyRef::Position(location) => Value::UInt(location.bits()),
+        PropertyRef::Orientation(RofiOrientation::Vertical) => Value::String("vertical".to_owned()),
+        PropertyRef::Orientation(RofiOrientation::Horizontal) => {
+            Value::String("horizontal".to_owned())
+        }
+        PropertyRef::Cursor(RofiCursorType::Default) => Value::String("default".to_owned()),
+        PropertyRef::Cursor(RofiCursorType::Pointer) => Value::String("pointer".to_owned()),
+        PropertyRef::Cursor(RofiCursorType::Text) => Value::String("text".to_owned()),
+        PropertyRef::Link {
+            resolved: Some(target),
+            ..
+        }
+        | PropertyRef::Link {
+            default: Some(target),
+            ..
+        } => unsafe { property_value(target) },
+        PropertyRef::Link { name, .. } => {
+            Value::Invalid(format!("unresolved link to `{}`", name.to_string_lossy()))
+        }
+        other => Value::Invalid(format!(
+            "properties of type {:?} are not supported (property `{}`)",
+            other.ty(),
+            unsafe { string(property.name) },
+        )),
+    }
+}
+
+fn color_value(color: ThemeColor) -> Value {
+    Value::Map(vec![
+        ("red".to_owned(), Value::Double(color.red)),
+        ("green".to_owned(), Value::Double(color.green)),
+        ("blue".to_owned(), Value::Double(color.blue)),
+        ("alpha".to_owned(), Value::Double(color.alpha)),
+    ])
+}
+
+fn distance_value(distance: RofiDistance) -> Value {
+    let base = distance.base;
+    if base.mod_type != RofiDistanceModifier::None || !base.left.is_null() || !base.right.is_null()
+    {
+        return Value::Invalid("calc() distances are not supported".to_owned());
+    }
+    let unit = match base.unit_type {
+        RofiPixelUnit::Px => "px",
+        RofiPixelUnit::Mm => "mm",
+        RofiPixelUnit::Em => "em",
+        RofiPixelUnit::Percent => "percent",
+        RofiPixelUnit::Ch => "ch",
+    };
+    let style = match distance.style {
+        RofiLineStyle::Solid => "solid",
+        RofiLineStyle::Dash => "dash",
+    };
+    Value::Map(vec![
+        ("value".to_owned(), Value::Double(base.distance)),
+        ("unit".to_owned(), Value::String(unit.to_owned())),
+        ("style".to_owned(), Value::String(style.to_owned())),
+    ])
+}
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Self::Int(i) => visitor.visit_i32(i),
+            Self::UInt(u) => visitor.visit_u32(u),
+            Self::Double(f) => visitor.visit_f64(f),
+            Self::String(s) => visitor.visit_string(s),
+            Self::Char(c) => visitor.visit_char(c),
+            Self::Bool(b) => visitor.visit_bool(b),
+            Self::List(items) => visitor.visit_seq(SeqDeserializer::new(items.into_iter())),
+            Self::Map(entries) => visitor.visit_map(MapDeserializer::new(entries.into_iter())),
+            Self::Invalid(message) => Err(de::Error::custom(message)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Self::String(s) => visitor.visit_enum(s.into_deserializer()),
+            Self::Invalid(message) => Err(de::Error::custom(message)),
+            _ => Err(de::Error::custom(format!(
+                "expected a string for enum `{name}`"
+            ))),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
@@ -0,0 +1,173 @@
+//! A port of Rofi's evaluation of theme distances to pixels,
+//! for plugins that render parts of their entries themselves.
+//!
+//! A [`RofiDistance`] is an expression tree built from `calc()` expressions,
+//! whose leaves are values in one of the [`RofiPixelUnit`]s.
+//! [`get_pixels`] evaluates it like Rofi's `distance_get_pixel`,
+//! including its integer arithmetic,
+//! with the font metrics and monitor size given explicitly in a [`DistanceContext`].
+//!
+//! ```
+//! use {
+//!     rofi_plugin_sys::{
+//!         distance::{self, DistanceContext},
+//!         RofiDistance, RofiDistanceModifier, RofiDistanceUnit, RofiLineStyle,
+//!         RofiOrientation, RofiPixelUnit,
+//!     },
+//!     std::ptr,
+//! };
+//!
+//! let leaf = |distance, unit_type| RofiDistanceUnit {
+//!     distance,
+//!     unit_type,
+//!     mod_type: RofiDistanceModifier::None,
+//!     left: ptr::null_mut(),
+//!     right: ptr::null_mut(),
+//! };
+//! let mut percent = leaf(50.0, RofiPixelUnit::Percent);
+//! let mut em = leaf(2.0, RofiPixelUnit::Em);
+//! // calc(50% - 2em)
+//! let distance = RofiDistance {
+//!     base: RofiDistanceUnit {
+//!         mod_type: RofiDistanceModifier::Subtract,
+//!         left: &mut percent,
+//!         right: &mut em,
+//!         ..leaf(0.0, RofiPixelUnit::Px)
+//!     },
+//!     style: RofiLineStyle::Solid,
+//! };
+//!
+//! let context = DistanceContext {
+//!     dpi: 96.0,
+//!     em: 16.0,
+//!     ch: 8.0,
+//!     width: 1920,
+//!     height: 1080,
+//! };
+//! let pixels = unsafe { distance::get_pixels(&distance, RofiOrientation::Horizontal, &context) };
+//! assert_eq!(pixels, 928);
+//! ```
+
+use {
+    crate::{RofiDistance, RofiDistanceModifier, RofiDistanceUnit, RofiOrientation, RofiPixelUnit},
+    ::std::os::raw::{c_double, c_int},
+};
+
+/// The values that units other than pixels are relative to.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DistanceContext {
+    /// The resolution of the monitor in dots per inch, for [`RofiPixelUnit::Mm`].
+    ///
+    /// Rofi stores the DPI it uses in [`settings::config`](crate::settings::config)`.dpi`.
+    pub dpi: c_double,
+    /// The estimated height of a character of the font in pixels, for [`RofiPixelUnit::Em`].
+    pub em: c_double,
+    /// The estimated width of a character of the font in pixels, for [`RofiPixelUnit::Ch`].
+    pub ch: c_double,
+    /// The width of the monitor in pixels,
+    /// which horizontal [`RofiPixelUnit::Percent`] values are relative to.
+    pub width: c_int,
+    /// The height of the monitor in pixels,
+    /// which vertical [`RofiPixelUnit::Percent`] values are relative to.
+    pub height: c_int,
+}
+
+/// Convert a single value in `unit` to pixels.
+///
+/// Percentages are relative to the width of the monitor
+/// for horizontal distances and to its height for vertical ones.
+/// The result is truncated towards zero, like in Rofi.
+pub fn unit_pixels(
+    value: c_double,
+    unit: RofiPixelUnit,
+    orientation: RofiOrientation,
+    context: &DistanceContext,
+) -> c_int {
+    let pixels = match unit {
+        RofiPixelUnit::Px => value,
+        RofiPixelUnit::Mm => value * context.dpi / 25.4,
+        RofiPixelUnit::Em => value * context.em,
+        RofiPixelUnit::Ch => value * context.ch,
+        RofiPixelUnit::Percent => {
+            let size = match orientation {
+                RofiOrientation::Vertical => context.height,
+                RofiOrientation::Horizontal => context.width,
+            };
+            value * c_double::from(size) / 100.0
+        }
+    };
+    pixels as c_int
+}
+
+/// Evaluate `distance` to pixels, like Rofi's `distance_get_pixel`.
+///
+/// `orientation` selects whether percentages are relative to the monitor's width or height:
+/// Rofi uses [`RofiOrientation::Horizontal`] for left and right paddings and widths,
+/// and [`RofiOrientation::Vertical`] for top and bottom paddings and heights.
+///
+/// Every operation is done on integers, like in Rofi.
+/// Dividing by zero gives the left operand and the remainder of a division by zero is 0,
+/// also like in Rofi, and rounding to a multiple of zero leaves the left operand unchanged.
+///
+/// # Safety
+///
+/// The `left` and `right` pointers of every node of the tree
+/// that is not a leaf must be null or point to valid [`RofiDistanceUnit`]s.
+/// Null operands evaluate to 0.
+pub unsafe fn get_pixels(
+    distance: &RofiDistance,
+    orientation: RofiOrientation,
+    context: &DistanceContext,
+) -> c_int {
+    unsafe { unit_get_pixels(&distance.base, orientation, context) }
+}
+
+unsafe fn unit_get_pixels(
+    unit: &RofiDistanceUnit,
+    orientation: RofiOrientation,
+    context: &DistanceContext,
+) -> c_int {
+    let operand = |operand: *const RofiDistanceUnit| match unsafe { operand.as_ref() } {
+        Some(operand) => unsafe { unit_get_pixels(operand, orientation, context) },
+        None => 0,
+    };
+    let left = || operand(unit.left);
+    let right = || operand(unit.right);
+    let rounded = |round: fn(c_double) -> c_double| {
+        let (a, b) = (c_double::from(left()), c_double::from(right()));
+        if b == 0.0 {
+            return a as c_int;
+        }
+        (round(a / b) * b) as c_int
+    };
+    match unit.mod_type {
+        RofiDistanceModifier::None => {
+            unit_pixels(unit.distance, unit.unit_type, orientation, context)
+        }
+        RofiDistanceModifier::Group => left(),
+        RofiDistanceModifier::Add => left().wrapping_add(right()),
+        RofiDistanceModifier::Subtract => left().wrapping_sub(right()),
+        RofiDistanceModifier::Multiply => left().wrapping_mul(right()),
+        RofiDistanceModifier::Divide => {
+            let (a, b) = (left(), right());
+            if b == 0 {
+                a
+            } else {
+                a.wrapping_div(b)
+            }
+        }
+        RofiDistanceModifier::Modulo => {
+            let (a, b) = (left(), right());
+            if b == 0 {
+                0
+            } else {
+                a.wrapping_rem(b)
+            }
+        }
+        RofiDistanceModifier::Min => left().min(right()),
+        RofiDistanceModifier::Max => left().max(right()),
+        RofiDistanceModifier::Round => rounded(c_double::round),
+        RofiDistanceModifier::Floor => rounded(c_double::floor),
+        RofiDistanceModifier::Ceil => rounded(c_double::ceil),
+    }
+}
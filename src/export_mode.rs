@@ -0,0 +1,175 @@
+//! The [`export_mode!`] macro.
+
+/// Declare the `mode` static that Rofi looks up when loading a plugin.
+///
+/// This expands to a `#[no_mangle] pub static mut mode: Mode`
+/// with [`abi_version`](crate::Mode::abi_version) set to [`ABI_VERSION`](crate::ABI_VERSION),
+/// `name`, `display_name` and `cfg_name_key` filled in from string literals,
+/// and the given callbacks set.
+/// Every field that is not given keeps its value from [`Mode::default`](crate::Mode::default);
+/// in particular, the mode type is [`ModeType::SWITCHER`](crate::ModeType::SWITCHER).
+///
+/// The `name` must be at most 31 bytes long
+/// and `cfg_name_key`, which defaults to `display-` followed by the name,
+/// at most 127 bytes long.
+/// None of the strings may contain NUL bytes.
+/// These are checked at compile time.
+///
+/// Other fields are given after these, in any order.
+/// Callbacks are given by the name of their `Mode` field without the leading underscore:
+/// `init`, `destroy`, `get_num_entries`, `result`, `token_match`, `get_display_value`,
+/// `get_icon`, `get_completion`, `preprocess_input`, `get_message` and `free`,
+/// as well as `create` and `completer_result`
+/// (**not available with the `rofi-1-7` feature**).
+/// The key can be changed with `cfg_name_key`
+/// and the mode type with `mode_type`
+/// (**not available with the `rofi-1-7` feature**).
+///
+/// ```
+/// use ::{
+///     rofi_plugin_sys as rofi,
+///     std::os::raw::{c_char, c_int, c_uint},
+/// };
+///
+/// rofi::export_mode! {
+///     name: "example",
+///     display_name: "An example mode",
+///     init: init,
+///     destroy: destroy,
+///     get_num_entries: get_num_entries,
+///     result: result,
+///     get_display_value: get_display_value,
+/// }
+///
+/// unsafe extern "C" fn init(_: *mut rofi::Mode) -> c_int {
+///     1
+/// }
+/// unsafe extern "C" fn destroy(_: *mut rofi::Mode) {}
+/// unsafe extern "C" fn get_num_entries(_: *const rofi::Mode) -> c_uint {
+///     0
+/// }
+/// unsafe extern "C" fn result(_: *mut rofi::Mode, _: c_int, _: *mut *mut c_char, _: c_uint) -> c_int {
+///     rofi::EXIT
+/// }
+/// unsafe extern "C" fn get_display_value(
+///     _: *const rofi::Mode,
+///     _: c_uint,
+///     _: *mut c_int,
+///     _: *mut *mut rofi::glib_sys::GList,
+///     _: c_int,
+/// ) -> *mut c_char {
+///     std::ptr::null_mut()
+/// }
+/// ```
+///
+/// Names that are too long are rejected:
+///
+/// ```compile_fail
+/// rofi_plugin_sys::export_mode! {
+///     name: "a-name-that-is-much-too-long-for-rofi",
+///     display_name: "Too long",
+/// }
+/// ```
+#[macro_export]
+macro_rules! export_mode {
+    (
+        name: $name:literal,
+        display_name: $display_name:literal
+        $(, $field:ident: $value:expr)* $(,)?
+    ) => {
+        #[no_mangle]
+        pub static mut mode: $crate::Mode = {
+            let mut __mode = $crate::Mode::default();
+            __mode.name = $crate::__private::mode_name(::core::concat!($name, "\0"));
+            __mode.display_name =
+                $crate::__private::display_name(::core::concat!($display_name, "\0"));
+            __mode.cfg_name_key = $crate::__private::cfg_name_key(::core::concat!("display-", $name));
+            $($crate::export_mode!(@field __mode, $field, $value);)*
+            __mode
+        };
+    };
+
+    (@field $mode:ident, cfg_name_key, $value:expr) => {
+        $mode.cfg_name_key = $crate::__private::cfg_name_key($value)
+    };
+    (@field $mode:ident, init, $value:expr) => { $mode._init = ::core::option::Option::Some($value) };
+    (@field $mode:ident, destroy, $value:expr) => { $mode._destroy = ::core::option::Option::Some($value) };
+    (@field $mode:ident, get_num_entries, $value:expr) => {
+        $mode._get_num_entries = ::core::option::Option::Some($value)
+    };
+    (@field $mode:ident, result, $value:expr) => { $mode._result = ::core::option::Option::Some($value) };
+    (@field $mode:ident, token_match, $value:expr) => {
+        $mode._token_match = ::core::option::Option::Some($value)
+    };
+    (@field $mode:ident, get_display_value, $value:expr) => {
+        $mode._get_display_value = ::core::option::Option::Some($value)
+    };
+    (@field $mode:ident, get_icon, $value:expr) => { $mode._get_icon = ::core::option::Option::Some($value) };
+    (@field $mode:ident, get_completion, $value:expr) => {
+        $mode._get_completion = ::core::option::Option::Some($value)
+    };
+    (@field $mode:ident, preprocess_input, $value:expr) => {
+        $mode._preprocess_input = ::core::option::Option::Some($value)
+    };
+    (@field $mode:ident, get_message, $value:expr) => {
+        $mode._get_message = ::core::option::Option::Some($value)
+    };
+    (@field $mode:ident, free, $value:expr) => { $mode.free = ::core::option::Option::Some($value) };
+    (@field $mode:ident, create, $value:expr) => { $mode._create = ::core::option::Option::Some($value) };
+    (@field $mode:ident, completer_result, $value:expr) => {
+        $mode._completer_result = ::core::option::Option::Some($value)
+    };
+    (@field $mode:ident, mode_type, $value:expr) => { $mode.r#type = $value };
+    (@field $mode:ident, $field:ident, $value:expr) => {
+        ::core::compile_error!(::core::concat!("unknown `Mode` field `", ::core::stringify!($field), "`"))
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use ::std::os::raw::c_char;
+
+    /// Check that a NUL-terminated string has no interior NUL bytes
+    /// and is at most `max_len` bytes long, not counting the terminator.
+    const fn check(s: &str, max_len: usize) -> bool {
+        let bytes = s.as_bytes();
+        let len = bytes.len() - 1;
+        let mut i = 0;
+        while i < len {
+            if bytes[i] == 0 {
+                panic!("Rofi mode strings may not contain NUL bytes");
+            }
+            i += 1;
+        }
+        len <= max_len
+    }
+
+    pub const fn mode_name(s: &'static str) -> *mut c_char {
+        if !check(s, 31) {
+            panic!("Rofi mode names may be at most 31 bytes long");
+        }
+        s.as_ptr() as *mut c_char
+    }
+
+    pub const fn display_name(s: &'static str) -> *mut c_char {
+        check(s, usize::MAX);
+        s.as_ptr() as *mut c_char
+    }
+
+    pub const fn cfg_name_key(s: &str) -> [c_char; 128] {
+        let bytes = s.as_bytes();
+        let mut key = [0; 128];
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == 0 {
+                panic!("Rofi mode strings may not contain NUL bytes");
+            }
+            if i == key.len() - 1 {
+                panic!("Rofi mode `cfg_name_key`s may be at most 127 bytes long");
+            }
+            key[i] = bytes[i] as c_char;
+            i += 1;
+        }
+        key
+    }
+}
@@ -3,13 +3,25 @@
 //! This corresponds to `helper.h`.
 
 use {
-    crate::{ConfigEntry, Property, PropertyType, RofiIntMatcher, RofiRangePair},
+    crate::{view::EntryBuffer, RofiIntMatcher, RofiRangePair},
     ::std::{
-        os::raw::{c_char, c_int, c_long, c_uint},
-        ptr::NonNull,
+        ffi::{CStr, CString},
+        fs::File,
+        io::{self, BufRead, BufReader},
+        marker::PhantomData,
+        mem,
+        os::{
+            raw::{c_char, c_int, c_long, c_uint},
+            unix::io::FromRawFd,
+        },
+        ptr::{self, NonNull},
+        slice, thread,
     },
 };
 
+#[cfg(feature = "pango")]
+use crate::RofiHighlightColorStyle;
+
 extern "C" {
     /// Parses a string into arguments, replacing keys with values.
     ///
@@ -324,28 +336,34 @@ extern "C" {
     /// - `name`: The name of the element to find.
     /// - `state`: The state of the element.
     /// - `exact`: If the match should be exact, or the parent can be included.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     #[link_name = "rofi_config_find_widget"]
     pub fn config_find_widget(
         name: *const c_char,
         state: *const c_char,
         exact: glib_sys::gboolean,
-    ) -> *mut ConfigEntry;
+    ) -> *mut crate::ConfigEntry;
 
     /// Find the property on the widget.
     /// If not exact, the parents are searched recursively until match is found.
-    /// Returns the [`Property`] if found, otherwise null.
+    /// Returns the [`Property`](crate::Property) if found, otherwise null.
     ///
     /// - `widget`: The widget to find the property on.
-    /// - `type`: The [`PropertyType`] to find.
+    /// - `type`: The [`PropertyType`](crate::PropertyType) to find.
     /// - `property`: The property to find.
     /// - `exact`: If the property should only be found on this widget, or on parents if not found.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     #[link_name = "rofi_theme_find_property"]
     pub fn theme_find_property(
-        widget: *mut ConfigEntry,
-        r#type: PropertyType,
+        widget: *mut crate::ConfigEntry,
+        r#type: crate::PropertyType,
         property: *const c_char,
         exact: glib_sys::gboolean,
-    ) -> *mut Property;
+    ) -> *mut crate::Property;
 
     /// Get a human-readable string with the current matching method.
     pub fn helper_get_matching_mode_str() -> *const c_char;
@@ -355,4 +373,472 @@ extern "C" {
 
     /// Switch to the previous matching method.
     pub fn helper_select_previous_matching_mode();
+
+    /// Creates a set of Pango attributes highlighting the parts of `input` matched by `tokens`.
+    ///
+    /// Returns `retv`, which must be a valid attribute list:
+    /// Rofi does not create one if it is null.
+    ///
+    /// - `th`: The highlight style.
+    /// - `tokens`: The tokens to match against.
+    /// - `input`: The input string.
+    /// - `retv`: The attribute list to add the attributes to.
+    ///
+    /// **Only available with the `pango` feature.**
+    #[cfg(feature = "pango")]
+    #[link_name = "helper_token_match_get_pango_attr"]
+    pub fn token_match_get_pango_attr(
+        th: RofiHighlightColorStyle,
+        tokens: *mut *mut RofiIntMatcher,
+        input: *const c_char,
+        retv: *mut pango_sys::PangoAttrList,
+    ) -> *mut pango_sys::PangoAttrList;
+
+    /// Adds Pango attributes for the highlight style `th` to the byte range `start..end`.
+    ///
+    /// - `retv`: The attribute list to add the attributes to.
+    /// - `start`: The start of the range, in bytes.
+    /// - `end`: The end of the range, in bytes.
+    /// - `th`: The highlight style.
+    ///
+    /// **Only available with the `pango` feature.**
+    #[cfg(feature = "pango")]
+    #[link_name = "helper_token_match_set_pango_attr_on_style"]
+    pub fn token_match_set_pango_attr_on_style(
+        retv: *mut pango_sys::PangoAttrList,
+        start: c_int,
+        end: c_int,
+        th: RofiHighlightColorStyle,
+    );
+}
+
+/// Score how well `pattern` matches `s` with [`scorer_fuzzy_evaluate`].
+///
+/// Returns the sorting weight: lower is better.
+/// See [`sorting::fuzzy_score`](crate::sorting::fuzzy_score) for a version that does not need Rofi.
+pub fn scorer_fuzzy_evaluate_str(pattern: &str, s: &str, case_sensitive: bool) -> c_int {
+    unsafe {
+        scorer_fuzzy_evaluate(
+            pattern.as_ptr().cast(),
+            char_len(pattern),
+            s.as_ptr().cast(),
+            char_len(s),
+            c_int::from(case_sensitive),
+        )
+    }
+}
+
+/// Get the [`levenshtein`] distance between `needle` and `haystack`.
+///
+/// See [`sorting::levenshtein`](crate::sorting::levenshtein) for a version that does not need Rofi.
+pub fn levenshtein_str(needle: &str, haystack: &str, case_sensitive: bool) -> c_uint {
+    unsafe {
+        levenshtein(
+            needle.as_ptr().cast(),
+            char_len(needle),
+            haystack.as_ptr().cast(),
+            char_len(haystack),
+            c_int::from(case_sensitive),
+        )
+    }
+}
+
+/// The number of characters in a string, which is what Rofi's functions take as the length.
+fn char_len(s: &str) -> c_long {
+    s.chars().count().try_into().unwrap_or(c_long::MAX)
+}
+
+/// Parse a comma-separated list of ranges such as `1,3-5,7-` with [`parse_ranges`].
+///
+/// Ranges are inclusive, except Python-style ranges such as `3:5`,
+/// and open ends of ranges are given as -1.
+///
+/// # Panics
+///
+/// Panics if `input` contains a NUL byte.
+pub fn parse_ranges_str(input: &str) -> Vec<RofiRangePair> {
+    let input = CString::new(input).expect("input contains a NUL byte");
+    let input = input.into_raw();
+    let mut list: *mut RofiRangePair = ptr::null_mut();
+    let mut length: c_uint = 0;
+    let ranges = unsafe {
+        parse_ranges(input, &mut list, &mut length);
+        drop(CString::from_raw(input));
+        if list.is_null() {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(list, length as usize).to_vec()
+        }
+    };
+    unsafe { glib_sys::g_free(list.cast()) };
+    ranges
+}
+
+/// Get whether matching `input` should be case-sensitive with [`parse_case_sensitivity`],
+/// according to the user's `-case-sensitive` and `-case-smart` options.
+///
+/// # Panics
+///
+/// Panics if `input` contains a NUL byte.
+pub fn parse_case_sensitivity_str(input: &str) -> bool {
+    let input = CString::new(input).expect("input contains a NUL byte");
+    unsafe { parse_case_sensitivity(input.as_ptr()) != 0 }
+}
+
+/// Run `cmd` through the user's `-run-command` with [`execute_generator`],
+/// and get the read end of its standard output.
+///
+/// Like Rofi's other functions, this should be called from Rofi's main thread.
+///
+/// Rofi reports commands that fail to start to the user itself,
+/// so the returned error only says that the command could not be run.
+/// It does not carry an OS error code, since `errno` does not hold one reliably:
+/// the most common failure, a program that does not exist,
+/// happens in the child process after the fork,
+/// and GLib passes its `errno` back in a `GError` that Rofi frees.
+/// By the time this function returns,
+/// `errno` is whatever Rofi's error dialog last left in it.
+///
+/// ```no_run
+/// use {
+///     rofi_plugin_sys::helper,
+///     std::io::{BufRead, BufReader},
+/// };
+///
+/// let output = helper::execute_generator_str("ls /usr/share/applications")?;
+/// for line in BufReader::new(output).lines() {
+///     println!("{}", line?);
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Panics
+///
+/// Panics if `cmd` contains a NUL byte.
+pub fn execute_generator_str(cmd: &str) -> io::Result<File> {
+    let cmd = CString::new(cmd).expect("command contains a NUL byte");
+    match unsafe { execute_generator(cmd.as_ptr()) } {
+        -1 => Err(io::Error::new(
+            io::ErrorKind::Other,
+            "failed to execute the command",
+        )),
+        fd => Ok(unsafe { File::from_raw_fd(fd) }),
+    }
+}
+
+/// Run `cmd` like [`execute_generator_str`],
+/// and stream the lines of its output into `entries` from a new thread.
+///
+/// The thread lives until the command closes its output,
+/// which may be never for commands like `tail -f`.
+/// It is not one of Rofi's [`pool`](crate::pool) workers,
+/// since Rofi waits for those to finish filtering on its main thread.
+///
+/// Each batch of lines requests a reload of the view,
+/// so the mode can [`take`](EntryBuffer::take) the new lines in its `_get_num_entries`.
+/// Invalid UTF-8 is replaced with `U+FFFD`.
+/// Reading stops at the end of the output or at the first error.
+///
+/// ```no_run
+/// use rofi_plugin_sys::{helper, view::EntryBuffer};
+///
+/// let entries = EntryBuffer::new();
+/// helper::execute_generator_lines("find /home/me/notes -name '*.md'", &entries)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Panics
+///
+/// Panics if `cmd` contains a NUL byte.
+pub fn execute_generator_lines(cmd: &str, entries: &EntryBuffer<String>) -> io::Result<()> {
+    let mut output = BufReader::new(execute_generator_str(cmd)?);
+    let entries = entries.clone();
+    let reader = thread::Builder::new().name("rofi-generator".to_owned());
+    reader.spawn(move || {
+        let mut batch = Vec::new();
+        let mut line = Vec::new();
+        while let Ok(1..) = output.read_until(b'\n', &mut line) {
+            if line.last() == Some(&b'\n') {
+                line.pop();
+            }
+            batch.push(String::from_utf8_lossy(&line).into_owned());
+            line.clear();
+            // Hand over what has been read so far whenever the command is slower than us.
+            if output.buffer().is_empty() {
+                entries.extend(mem::take(&mut batch));
+            }
+        }
+        entries.extend(batch);
+    })?;
+    Ok(())
+}
+
+/// The command-line arguments Rofi was started with,
+/// read through the `find_arg*` family of functions.
+///
+/// Keys are given as on the command line, including the leading `-`.
+///
+/// ```no_run
+/// use rofi_plugin_sys::helper::Args;
+///
+/// let args = Args::new();
+/// let depth = args.get::<u32>("-my-plugin-depth").unwrap_or(3);
+/// let paths = args.get_all("-my-plugin-path");
+/// let verbose = args.has("-my-plugin-verbose");
+/// ```
+///
+/// # Panics
+///
+/// All methods panic if the key contains a NUL byte.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Args {
+    _private: (),
+}
+
+impl Args {
+    /// Access the command-line arguments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check whether `key` was passed.
+    pub fn has(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Get the position of `key` among the arguments, with [`find_arg`].
+    /// The program name is at position 0.
+    pub fn position(&self, key: &str) -> Option<usize> {
+        let key = arg_key(key);
+        usize::try_from(unsafe { find_arg(key.as_ptr()) }).ok()
+    }
+
+    /// Get the value following `key`, parsed as a `T`.
+    ///
+    /// Returns `None` if `key` was not passed or has no value.
+    pub fn get<T: FromArg>(&self, key: &str) -> Option<T> {
+        T::find_arg(&arg_key(key))
+    }
+
+    /// Get the values following every occurrence of `key`, with [`find_arg_strv`].
+    pub fn get_all(&self, key: &str) -> Vec<String> {
+        let key = arg_key(key);
+        let strv = unsafe { find_arg_strv(key.as_ptr()) };
+        if strv.is_null() {
+            return Vec::new();
+        }
+        let mut values = Vec::new();
+        unsafe {
+            let mut value = strv;
+            while !(*value).is_null() {
+                values.push(CStr::from_ptr(*value).to_string_lossy().into_owned());
+                value = value.add(1);
+            }
+            // The strings belong to the arguments; only the array is ours.
+            glib_sys::g_free(strv.cast());
+        }
+        values
+    }
+
+    /// Get the value following `key`, parsed as ranges with [`parse_ranges_str`].
+    pub fn get_ranges(&self, key: &str) -> Option<Vec<RofiRangePair>> {
+        self.get::<String>(key)
+            .map(|ranges| parse_ranges_str(&ranges))
+    }
+}
+
+fn arg_key(key: &str) -> CString {
+    CString::new(key).expect("argument key contains a NUL byte")
+}
+
+/// A type that can be read from the command line with [`Args::get`].
+pub trait FromArg: Sized {
+    /// Find the value following `key` on the command line and parse it.
+    fn find_arg(key: &CStr) -> Option<Self>;
+}
+
+/// Read with [`find_arg_str`]; non-UTF-8 values are converted lossily.
+impl FromArg for String {
+    fn find_arg(key: &CStr) -> Option<Self> {
+        let mut value = ptr::null_mut();
+        if unsafe { find_arg_str(key.as_ptr(), &mut value) } == 0 || value.is_null() {
+            return None;
+        }
+        Some(
+            unsafe { CStr::from_ptr(value) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+}
+
+/// Read with [`find_arg_int`].
+impl FromArg for i32 {
+    fn find_arg(key: &CStr) -> Option<Self> {
+        let mut value = 0;
+        (unsafe { find_arg_int(key.as_ptr(), &mut value) } != 0).then(|| value)
+    }
+}
+
+/// Read with [`find_arg_uint`].
+impl FromArg for u32 {
+    fn find_arg(key: &CStr) -> Option<Self> {
+        let mut value = 0;
+        (unsafe { find_arg_uint(key.as_ptr(), &mut value) } != 0).then(|| value)
+    }
+}
+
+/// Read with [`find_arg_char`], which supports escapes such as `\n` and `\x1f`.
+/// The resulting byte is interpreted as Latin-1.
+impl FromArg for char {
+    fn find_arg(key: &CStr) -> Option<Self> {
+        let mut value = 0;
+        (unsafe { find_arg_char(key.as_ptr(), &mut value) } != 0).then(|| char::from(value as u8))
+    }
+}
+
+/// An owned set of tokens created by [`tokenize`], freed with [`tokenize_free`] on drop.
+///
+/// This gives access to Rofi's matching engine,
+/// with the matching method (normal, regex, glob, fuzzy or prefix) configured by the user,
+/// for filtering data other than the mode's entries.
+#[derive(Debug)]
+pub struct Tokens {
+    /// Null if the input had no tokens.
+    ptr: *mut *mut RofiIntMatcher,
+}
+
+impl Tokens {
+    /// Split the user input into tokens on spaces.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` contains a NUL byte.
+    pub fn new(input: &str, case_sensitive: bool) -> Self {
+        let input = CString::new(input).expect("input contains a NUL byte");
+        let ptr = unsafe { tokenize(input.as_ptr(), c_int::from(case_sensitive)) };
+        Self { ptr }
+    }
+
+    /// Get the raw token array, as passed to [`ModeTokenMatch`](crate::ModeTokenMatch).
+    ///
+    /// This is null if there are no tokens.
+    pub fn as_ptr(&self) -> *mut *mut RofiIntMatcher {
+        self.ptr
+    }
+
+    /// Iterate over the matchers of the tokens.
+    pub fn iter(&self) -> TokensIter<'_> {
+        TokensIter {
+            ptr: self.ptr,
+            _tokens: PhantomData,
+        }
+    }
+
+    /// Get the number of tokens.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Whether there are no tokens, in which case everything matches.
+    pub fn is_empty(&self) -> bool {
+        self.ptr.is_null()
+    }
+
+    /// Check whether `input` matches every token.
+    ///
+    /// Input containing NUL bytes is only matched up to the first one.
+    pub fn matches(&self, input: &str) -> bool {
+        let input = match CString::new(input) {
+            Ok(input) => input,
+            Err(e) => {
+                let nul = e.nul_position();
+                CString::new(&e.into_vec()[..nul]).unwrap()
+            }
+        };
+        self.matches_c_str(&input)
+    }
+
+    /// Check whether `input` matches every token.
+    pub fn matches_c_str(&self, input: &CStr) -> bool {
+        unsafe { token_match(self.ptr, input.as_ptr()) != 0 }
+    }
+}
+
+impl Drop for Tokens {
+    fn drop(&mut self) {
+        unsafe { tokenize_free(self.ptr) };
+    }
+}
+
+impl<'a> IntoIterator for &'a Tokens {
+    type Item = &'a RofiIntMatcher;
+    type IntoIter = TokensIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the matchers in [`Tokens`].
+#[derive(Debug, Clone)]
+pub struct TokensIter<'a> {
+    /// Points to the next matcher in a null-terminated array, or is null when finished.
+    ptr: *mut *mut RofiIntMatcher,
+    _tokens: PhantomData<&'a Tokens>,
+}
+
+impl<'a> Iterator for TokensIter<'a> {
+    type Item = &'a RofiIntMatcher;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ptr.is_null() {
+            return None;
+        }
+        let matcher = unsafe { *self.ptr };
+        if matcher.is_null() {
+            self.ptr = ptr::null_mut();
+            return None;
+        }
+        self.ptr = unsafe { self.ptr.add(1) };
+        Some(unsafe { &*matcher })
+    }
+}
+
+/// Append attributes highlighting the parts of `input` matched by `tokens`
+/// to the `attribute_list` out parameter of [`ModeGetDisplayValue`](crate::ModeGetDisplayValue),
+/// giving the same match highlighting as Rofi's built-in modes.
+///
+/// Does nothing if `attribute_list` or `tokens` is null.
+///
+/// **Only available with the `pango` feature.**
+///
+/// # Safety
+///
+/// `attribute_list` must be null or a valid pointer to a (possibly empty) list of `PangoAttribute`s,
+/// and `tokens` must be null or a token array as returned by [`tokenize`].
+#[cfg(feature = "pango")]
+pub unsafe fn append_highlight_attributes(
+    attribute_list: *mut *mut glib_sys::GList,
+    tokens: *mut *mut RofiIntMatcher,
+    style: RofiHighlightColorStyle,
+    input: &CStr,
+) {
+    if attribute_list.is_null() || tokens.is_null() {
+        return;
+    }
+    unsafe {
+        let list = pango_sys::pango_attr_list_new();
+        token_match_get_pango_attr(style, tokens, input.as_ptr(), list);
+        // The returned attributes are copies owned by us,
+        // which we hand over to the caller's list.
+        let attributes = pango_sys::pango_attr_list_get_attributes(list);
+        let mut node = attributes;
+        while !node.is_null() {
+            *attribute_list = glib_sys::g_list_append(*attribute_list, (*node).data);
+            node = (*node).next;
+        }
+        glib_sys::g_slist_free(attributes);
+        pango_sys::pango_attr_list_unref(list);
+    }
 }
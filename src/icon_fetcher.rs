@@ -46,6 +46,9 @@ extern "C" {
     /// Returns whether the query succeeded.
     ///
     /// Accepts a request UID and an out pointer.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     #[link_name = "rofi_icon_fetcher_get_ex"]
     pub fn get_ex(uid: u32, surface: *mut *mut cairo_sys::cairo_surface_t) -> glib_sys::gboolean;
 
@@ -8,17 +8,77 @@
 //! you'll probably want an easier-to-use library instead,
 //! such as [rofi-mode](https://docs.rs/rofi-mode).
 //!
-//! Set `RUSTFLAGS="--cfg rofi_next"` when building
-//! to change the API to use unreleased Rofi features
-//! (as of 2025-01-08).
-//! Items only available when this is turned on will be marked
-//! “**Semver-exempt and only available with `cfg(rofi_next)`.**”
-//! in the documentation.
+//! By default, the bindings target Rofi 2.0 (ABI version 7).
+//! Other versions can be targeted by enabling one of these features:
+//!
+//! - `rofi-1-7`: Rofi 1.7.0 to 1.7.5 (ABI version 6).
+//!   Items not available with this feature will be marked
+//!   “**Not available with the `rofi-1-7` feature.**”
+//!   in the documentation.
+//! - `rofi-2-0`: Rofi 2.0, as well as Rofi 1.7.6 and later which share its ABI (version 7).
+//!   This is the default, but can be enabled explicitly
+//!   to guard against another crate selecting a different version.
+//! - `rofi-next`: unreleased Rofi features (as of 2025-01-08).
+//!   This can also be enabled by setting `RUSTFLAGS="--cfg rofi_next"` when building.
+//!   Rofi's development branch currently has the same plugin API as Rofi 2.0,
+//!   so for now this selects the same bindings as `rofi-2-0`.
+//!   Items only available when this is turned on will be marked
+//!   “**Semver-exempt and only available with `cfg(rofi_next)`.**”
+//!   in the documentation.
+//!
+//! At most one of these features may be enabled.
+//! When none is enabled, the version can also be chosen
+//! by setting the `ROFI_PLUGIN_SYS_TARGET` environment variable at build time
+//! to `1.7`, `2.0`, `next` or a Rofi version number.
+//! Otherwise, if the `detect` feature is enabled,
+//! the build script asks `pkg-config` for the installed Rofi
+//! and targets the ABI version declared in its `rofi/mode-private.h`,
+//! falling back to Rofi 2.0 with a warning if it cannot be found.
+//! The Wayland fork of Rofi uses the same plugin ABI
+//! as the upstream version it is based on.
+//!
+//! The `pango` feature adds a dependency on `pango-sys`
+//! and enables the bindings that use Pango types,
+//! such as `helper::token_match_get_pango_attr`.
+//!
+//! The `serde` feature adds the `de` module,
+//! which deserializes plugin configuration sections of `config.rasi` into Rust types.
+//!
+//! The `gio` feature adds dependencies on `gio-sys` and `gobject-sys`
+//! and the `watch` module,
+//! which reloads a mode when the files it lists change.
+//!
+//! Enable the `mock` feature in your dev-dependencies
+//! to have this crate provide an in-process Rofi host for testing;
+//! see the `mock` module for more.
+//! [The harness folder] contains a command-line tool
+//! that loads a compiled plugin into the mock host and drives it,
+//! for smoke-testing plugins on machines without Rofi.
 //!
 //! [the examples folder]: https://github.com/SabrinaJewson/rofi-plugin-sys.rs/tree/main/examples
+//! [The harness folder]: https://github.com/SabrinaJewson/rofi-plugin-sys.rs/tree/main/harness
+
+// The features are checked directly rather than through the `cfg`s set by the build script,
+// since the build script only sets the `cfg` of the first target it finds.
+#[cfg(any(
+    all(feature = "rofi-1-7", feature = "rofi-2-0"),
+    all(feature = "rofi-1-7", any(feature = "rofi-next", rofi_next)),
+    all(feature = "rofi-2-0", any(feature = "rofi-next", rofi_next)),
+))]
+compile_error!(
+    "conflicting Rofi versions selected: \
+     enable at most one of the `rofi-1-7`, `rofi-2-0` and `rofi-next` features \
+     (`--cfg rofi_next` counts as `rofi-next`)"
+);
 
 pub use {cairo_sys, glib_sys};
 
+#[cfg(feature = "pango")]
+pub use pango_sys;
+
+#[cfg(feature = "gio")]
+pub use {gio_sys, gobject_sys};
+
 mod types;
 pub use types::*;
 
@@ -33,3 +93,32 @@ pub mod icon_fetcher;
 pub mod helper;
 
 pub mod view;
+
+pub mod settings;
+
+pub mod options;
+
+pub mod distance;
+
+pub mod rasi;
+
+pub mod pool;
+
+pub mod timer;
+
+#[cfg(feature = "gio")]
+pub mod watch;
+
+#[cfg(feature = "serde")]
+pub mod de;
+
+pub mod matching;
+
+pub mod sorting;
+
+mod export_mode;
+#[doc(hidden)]
+pub use export_mode::__private;
+
+#[cfg(feature = "mock")]
+pub mod mock;
@@ -0,0 +1,385 @@
+//! A reimplementation of Rofi's token matching that does not need Rofi.
+//!
+//! Rofi only exposes its matching through [`helper::tokenize`](crate::helper::tokenize),
+//! which is part of the `rofi` binary and reads the user's configuration.
+//! This module reproduces the same semantics over `&str`,
+//! with the configuration given explicitly:
+//! the input is split into tokens on spaces,
+//! each token is turned into a regular expression according to the [`MatchingMethod`]
+//! and compiled with GLib's `GRegex` exactly like Rofi does,
+//! and an entry matches if it matches every token
+//! (or does not match it, for tokens starting with the negation character).
+//!
+//! Unlike Rofi's tokens, [`Matchers`] are `Send` and `Sync`,
+//! so they can be used to filter large data sets off the main thread.
+//! They can also be converted into the raw token arrays
+//! passed to [`ModeTokenMatch`](crate::ModeTokenMatch),
+//! to test a mode's `_token_match` without Rofi.
+//!
+//! ```
+//! use rofi_plugin_sys::matching::{Matchers, MatchingConfig, MatchingMethod};
+//!
+//! let config = MatchingConfig {
+//!     method: MatchingMethod::Fuzzy,
+//!     ..MatchingConfig::default()
+//! };
+//! let matchers = Matchers::new("fb -baz", &config);
+//! assert!(matchers.matches("foo bar"));
+//! assert!(!matchers.matches("foo bar baz"));
+//! assert!(!matchers.matches("qux"));
+//! ```
+
+use {
+    crate::RofiIntMatcher,
+    ::std::{
+        ffi::CString,
+        fmt::{self, Debug, Formatter},
+        mem,
+        os::raw::c_char,
+        ptr, slice,
+    },
+};
+
+/// The way each token is turned into a regular expression,
+/// corresponding to Rofi's `-matching` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MatchingMethod {
+    /// The token matches anywhere in the entry.
+    #[default]
+    Normal,
+    /// The token is a regular expression (with PCRE syntax).
+    /// Invalid regular expressions are matched literally.
+    Regex,
+    /// The token is a glob pattern, where `*` matches any characters
+    /// and `?` matches a single non-whitespace character.
+    Glob,
+    /// The characters of the token must appear in order, but not necessarily consecutively.
+    Fuzzy,
+    /// The token matches at the start of a word.
+    Prefix,
+}
+
+impl MatchingMethod {
+    /// Parse the name used for the method in Rofi's `-matching` option.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "normal" => Self::Normal,
+            "regex" => Self::Regex,
+            "glob" => Self::Glob,
+            "fuzzy" => Self::Fuzzy,
+            "prefix" => Self::Prefix,
+            _ => return None,
+        })
+    }
+
+    /// Get the name used for the method in Rofi's `-matching` option.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Regex => "regex",
+            Self::Glob => "glob",
+            Self::Fuzzy => "fuzzy",
+            Self::Prefix => "prefix",
+        }
+    }
+}
+
+/// The configuration options that affect matching.
+///
+/// The defaults are the same as Rofi's.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchingConfig {
+    /// The matching method (`-matching`).
+    pub method: MatchingMethod,
+    /// Whether to split the input into tokens on spaces (`-tokenize`).
+    /// If not, the whole input is a single token.
+    pub tokenize: bool,
+    /// The character that, at the start of a token, inverts it (`-matching-negate-char`).
+    pub negate_char: Option<char>,
+    /// Whether matching is case-sensitive (`-case-sensitive`).
+    pub case_sensitive: bool,
+    /// Whether matching is case-sensitive only when the input contains uppercase characters
+    /// (`-case-smart`). This overrides `case_sensitive`.
+    pub case_smart: bool,
+}
+
+impl Default for MatchingConfig {
+    fn default() -> Self {
+        Self {
+            method: MatchingMethod::Normal,
+            tokenize: true,
+            negate_char: Some('-'),
+            case_sensitive: false,
+            case_smart: false,
+        }
+    }
+}
+
+impl MatchingConfig {
+    /// Whether matching the given input is case-sensitive,
+    /// as computed by [`helper::parse_case_sensitivity`](crate::helper::parse_case_sensitivity).
+    pub fn is_case_sensitive(&self, input: &str) -> bool {
+        if self.case_smart {
+            input.chars().any(char::is_uppercase)
+        } else {
+            self.case_sensitive
+        }
+    }
+}
+
+/// Escape all the special characters of a regular expression,
+/// like `g_regex_escape_string`.
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' | '|' | '(' | ')' | '[' | ']' | '{' | '}' | '^' | '$' | '*' | '+' | '?' | '.' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\0' => escaped.push_str("\\0"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Get the regular expression Rofi uses for a token (without the negation character).
+///
+/// For [`MatchingMethod::Regex`] this is the token itself;
+/// it is only escaped when it fails to compile.
+pub fn pattern(token: &str, method: MatchingMethod) -> String {
+    match method {
+        MatchingMethod::Normal => escape(token),
+        MatchingMethod::Regex => token.to_owned(),
+        MatchingMethod::Glob => {
+            let escaped = escape(token);
+            let mut pattern = String::with_capacity(escaped.len());
+            let mut chars = escaped.chars();
+            while let Some(c) = chars.next() {
+                if c != '\\' {
+                    pattern.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('*') => pattern.push_str(".*"),
+                    Some('?') => pattern.push_str("\\S"),
+                    Some(c) => {
+                        pattern.push('\\');
+                        pattern.push(c);
+                    }
+                    None => pattern.push('\\'),
+                }
+            }
+            pattern
+        }
+        MatchingMethod::Fuzzy => {
+            let escaped = escape(token);
+            let mut pattern = String::with_capacity(escaped.len() * 5);
+            let mut chars = escaped.chars();
+            let mut first = true;
+            while let Some(mut c) = chars.next() {
+                pattern.push_str(if first { "(" } else { ".*?(" });
+                first = false;
+                if c == '\\' {
+                    pattern.push('\\');
+                    match chars.next() {
+                        Some(next) => c = next,
+                        None => break,
+                    }
+                }
+                pattern.push(c);
+                pattern.push(')');
+            }
+            pattern
+        }
+        MatchingMethod::Prefix => format!("\\b{}", escape(token)),
+    }
+}
+
+/// Compile a regular expression with the flags Rofi uses, returning null on failure.
+fn compile(pattern: &str, case_sensitive: bool) -> *mut glib_sys::GRegex {
+    let pattern = match CString::new(pattern) {
+        Ok(pattern) => pattern,
+        Err(_) => return ptr::null_mut(),
+    };
+    let mut flags = glib_sys::G_REGEX_OPTIMIZE;
+    if !case_sensitive {
+        flags |= glib_sys::G_REGEX_CASELESS;
+    }
+    unsafe { glib_sys::g_regex_new(pattern.as_ptr(), flags, 0, ptr::null_mut()) }
+}
+
+/// A single token, equivalent to a [`RofiIntMatcher`] created by Rofi.
+pub struct Matcher {
+    /// Null if the pattern failed to compile, in which case nothing matches.
+    regex: *mut glib_sys::GRegex,
+    invert: bool,
+}
+
+// `GRegex` is immutable and reference-counted atomically, so it can be shared between threads.
+unsafe impl Send for Matcher {}
+unsafe impl Sync for Matcher {}
+
+impl Matcher {
+    /// Create the matcher for a single token.
+    pub fn new(
+        mut token: &str,
+        method: MatchingMethod,
+        negate_char: Option<char>,
+        case_sensitive: bool,
+    ) -> Self {
+        let mut invert = false;
+        if let Some(rest) = negate_char.and_then(|c| token.strip_prefix(c)) {
+            invert = true;
+            token = rest;
+        }
+        let mut regex = compile(&pattern(token, method), case_sensitive);
+        if regex.is_null() && method == MatchingMethod::Regex {
+            regex = compile(&escape(token), case_sensitive);
+        }
+        Self { regex, invert }
+    }
+
+    /// Whether the token was prefixed by the negation character,
+    /// so that it only matches entries that do not match its regular expression.
+    pub fn invert(&self) -> bool {
+        self.invert
+    }
+
+    /// Get the compiled regular expression, or null if it failed to compile.
+    pub fn regex(&self) -> *mut glib_sys::GRegex {
+        self.regex
+    }
+
+    /// Check whether an entry matches this token.
+    pub fn is_match(&self, input: &str) -> bool {
+        let matched = !self.regex.is_null()
+            && unsafe {
+                glib_sys::g_regex_match_full(
+                    self.regex,
+                    input.as_ptr().cast::<c_char>(),
+                    input.len() as isize,
+                    0,
+                    0,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                )
+            } != 0;
+        matched != self.invert
+    }
+
+    /// Convert this matcher into a [`RofiIntMatcher`] allocated with GLib,
+    /// which can be freed as part of a token array by [`helper::tokenize_free`](crate::helper::tokenize_free).
+    pub fn into_raw(self) -> *mut RofiIntMatcher {
+        let matcher = RofiIntMatcher {
+            regex: self.regex,
+            invert: glib_sys::gboolean::from(self.invert),
+        };
+        mem::forget(self);
+        let ptr: *mut RofiIntMatcher =
+            unsafe { glib_sys::g_malloc0_n(1, mem::size_of::<RofiIntMatcher>()) }.cast();
+        unsafe { ptr.write(matcher) };
+        ptr
+    }
+}
+
+impl Drop for Matcher {
+    fn drop(&mut self) {
+        if !self.regex.is_null() {
+            unsafe { glib_sys::g_regex_unref(self.regex) };
+        }
+    }
+}
+
+impl Debug for Matcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let pattern = if self.regex.is_null() {
+            None
+        } else {
+            let pattern = unsafe { glib_sys::g_regex_get_pattern(self.regex) };
+            Some(unsafe { ::std::ffi::CStr::from_ptr(pattern) }.to_string_lossy())
+        };
+        f.debug_struct("Matcher")
+            .field("pattern", &pattern)
+            .field("invert", &self.invert)
+            .finish()
+    }
+}
+
+/// The tokens of a user input, equivalent to the result of [`helper::tokenize`](crate::helper::tokenize).
+#[derive(Debug, Default)]
+pub struct Matchers {
+    matchers: Vec<Matcher>,
+}
+
+impl Matchers {
+    /// Tokenize the user input.
+    ///
+    /// Case sensitivity is determined with [`MatchingConfig::is_case_sensitive`].
+    pub fn new(input: &str, config: &MatchingConfig) -> Self {
+        let case_sensitive = config.is_case_sensitive(input);
+        let matcher =
+            |token| Matcher::new(token, config.method, config.negate_char, case_sensitive);
+        let matchers = if input.is_empty() {
+            Vec::new()
+        } else if config.tokenize {
+            input
+                .split(' ')
+                .filter(|token| !token.is_empty())
+                .map(matcher)
+                .collect()
+        } else {
+            vec![matcher(input)]
+        };
+        Self { matchers }
+    }
+
+    /// Iterate over the matchers of the tokens.
+    pub fn iter(&self) -> slice::Iter<'_, Matcher> {
+        self.matchers.iter()
+    }
+
+    /// Get the number of tokens.
+    pub fn len(&self) -> usize {
+        self.matchers.len()
+    }
+
+    /// Whether there are no tokens, in which case everything matches.
+    pub fn is_empty(&self) -> bool {
+        self.matchers.is_empty()
+    }
+
+    /// Check whether an entry matches every token,
+    /// like [`helper::token_match`](crate::helper::token_match).
+    pub fn matches(&self, input: &str) -> bool {
+        self.matchers.iter().all(|matcher| matcher.is_match(input))
+    }
+
+    /// Convert the tokens into a null-terminated array allocated with GLib,
+    /// as returned by [`helper::tokenize`](crate::helper::tokenize)
+    /// and freed with [`helper::tokenize_free`](crate::helper::tokenize_free).
+    ///
+    /// Like Rofi, this returns null if there are no tokens.
+    pub fn into_raw(self) -> *mut *mut RofiIntMatcher {
+        if self.matchers.is_empty() {
+            return ptr::null_mut();
+        }
+        let len = self.matchers.len();
+        let array: *mut *mut RofiIntMatcher =
+            unsafe { glib_sys::g_malloc0_n(len + 1, mem::size_of::<*mut RofiIntMatcher>()) }.cast();
+        for (i, matcher) in self.matchers.into_iter().enumerate() {
+            unsafe { *array.add(i) = matcher.into_raw() };
+        }
+        array
+    }
+}
+
+impl<'a> IntoIterator for &'a Matchers {
+    type Item = &'a Matcher;
+    type IntoIter = slice::Iter<'a, Matcher>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
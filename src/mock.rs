@@ -0,0 +1,1323 @@
+//! An in-process implementation of the Rofi host, for testing plugins without Rofi.
+//!
+//! **Only available with the `mock` feature.**
+//!
+//! Normally every function in [`mode`](crate::mode_init), [`helper`](crate::helper),
+//! [`view`](crate::view), [`icon_fetcher`](crate::icon_fetcher) and [`options`](crate::options)
+//! is provided by the `rofi` binary, so test binaries that reference them fail to link.
+//! Enabling this feature makes this crate define those symbols itself,
+//! following the behaviour of Rofi's own implementations
+//! closely enough that a plugin's [`Mode`] can be driven from `cargo test`.
+//! This feature should only be enabled as a dev-dependency;
+//! a plugin built with it would shadow Rofi's own functions.
+//!
+//! Calls with side effects on the view (such as [`view::reload`](crate::view::reload))
+//! are recorded and can be inspected with [`take_calls`].
+//! All host state is thread-local,
+//! mirroring the fact that Rofi calls into its modes from a single thread.
+//!
+//! The [`settings::config`] global is also defined,
+//! holding Rofi's default configuration.
+//! It is shared by all threads and is not reset by [`reset`].
+//! So is the [`tpool`](crate::tpool) global used by [`pool`](crate::pool),
+//! which is only created once [`init_workers`] is called.
+//!
+//! Not every host function is provided;
+//! those that are missing will still fail to link.
+//!
+//! ```
+//! use ::{
+//!     rofi_plugin_sys::{self as rofi, mock},
+//!     std::{
+//!         os::raw::{c_char, c_int, c_uint},
+//!         ptr,
+//!     },
+//! };
+//!
+//! unsafe extern "C" fn init(_: *mut rofi::Mode) -> c_int {
+//!     1
+//! }
+//! unsafe extern "C" fn destroy(_: *mut rofi::Mode) {}
+//! unsafe extern "C" fn get_num_entries(_: *const rofi::Mode) -> c_uint {
+//!     1
+//! }
+//! unsafe extern "C" fn get_display_value(
+//!     _: *const rofi::Mode,
+//!     _: c_uint,
+//!     _: *mut c_int,
+//!     _: *mut *mut rofi::glib_sys::GList,
+//!     get_entry: c_int,
+//! ) -> *mut c_char {
+//!     if get_entry != 0 {
+//!         unsafe { rofi::glib_sys::g_strdup("entry\0".as_ptr().cast()) }
+//!     } else {
+//!         ptr::null_mut()
+//!     }
+//! }
+//! unsafe extern "C" fn result(
+//!     _: *mut rofi::Mode,
+//!     _: c_int,
+//!     _: *mut *mut c_char,
+//!     _: c_uint,
+//! ) -> c_int {
+//!     unsafe { rofi::view::reload() };
+//!     rofi::RELOAD_DIALOG
+//! }
+//!
+//! let mut mode = rofi::Mode {
+//!     name: "test\0".as_ptr() as _,
+//!     _init: Some(init),
+//!     _destroy: Some(destroy),
+//!     _get_num_entries: Some(get_num_entries),
+//!     _get_display_value: Some(get_display_value),
+//!     _result: Some(result),
+//!     ..rofi::Mode::default()
+//! };
+//!
+//! let mut driver = unsafe { mock::Driver::new(&mut mode) };
+//! assert!(driver.init());
+//! assert_eq!(driver.entries(), ["entry"]);
+//! let (next, _) = driver.result(rofi::menu::OK, "", 0);
+//! assert_eq!(next, rofi::RELOAD_DIALOG);
+//! assert_eq!(mock::take_calls(), [mock::Call::Reload]);
+//! driver.destroy();
+//! ```
+
+use {
+    crate::{
+        matching::{Matchers, MatchingConfig},
+        menu,
+        options::XrmOptionType,
+        settings::{self, MatchingMethod, Settings, SortingMethod},
+        sorting, EntryState, GModule, Mode, PropertyType, RofiIntMatcher, RofiRangePair,
+        ThreadState, WindowLocation, NEXT_DIALOG, PREVIOUS_DIALOG,
+    },
+    ::std::{
+        cell::RefCell,
+        ffi::{c_void, CStr, CString},
+        mem,
+        os::raw::{c_char, c_int, c_long, c_uint},
+        path::Path,
+        ptr,
+        sync::Once,
+        thread,
+    },
+};
+
+/// A call made by the plugin to the host that has an observable effect on Rofi.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Call {
+    /// [`view::reload`](crate::view::reload) was called.
+    Reload,
+    /// [`view::hide`](crate::view::hide) was called.
+    Hide,
+    /// [`icon_fetcher::query`](crate::icon_fetcher::query)
+    /// or [`icon_fetcher::query_advanced`](crate::icon_fetcher::query_advanced) was called.
+    IconQuery {
+        /// The name of the requested icon.
+        name: String,
+        /// The requested width.
+        wsize: c_int,
+        /// The requested height.
+        hsize: c_int,
+    },
+}
+
+struct Host {
+    calls: Vec<Call>,
+    /// The arguments set by `cmd_set_arguments`.
+    argc: c_int,
+    argv: *mut *mut c_char,
+    /// Storage for arguments set by `set_arguments`.
+    owned_arguments: (Vec<CString>, Vec<*mut c_char>),
+    icon_uid: u32,
+    /// The configuration used by `helper_tokenize`.
+    matching: MatchingConfig,
+    /// The options added by `config_parser_add_option`.
+    options: Vec<XrmOption>,
+}
+
+struct XrmOption {
+    ty: XrmOptionType,
+    key: *const c_char,
+    value: *mut *mut c_void,
+    /// The current string value of a string option, freed when it is replaced.
+    mem: *mut c_char,
+}
+
+impl Default for Host {
+    fn default() -> Self {
+        Self {
+            calls: Vec::new(),
+            argc: 0,
+            argv: ptr::null_mut(),
+            owned_arguments: Default::default(),
+            icon_uid: 0,
+            matching: MatchingConfig::default(),
+            options: Vec::new(),
+        }
+    }
+}
+
+thread_local! {
+    static HOST: RefCell<Host> = RefCell::new(Host::default());
+}
+
+fn record(call: Call) {
+    HOST.with(|host| host.borrow_mut().calls.push(call));
+}
+
+/// Take all the calls recorded on this thread since the last call to this function.
+pub fn take_calls() -> Vec<Call> {
+    HOST.with(|host| mem::take(&mut host.borrow_mut().calls))
+}
+
+/// Set the command-line arguments seen by the `find_arg*` family of functions on this thread.
+///
+/// `args` should not include the program name; `rofi` is prepended automatically.
+pub fn set_arguments<I, S>(args: I)
+where
+    I: IntoIterator<Item = S>,
+    S: Into<Vec<u8>>,
+{
+    let arguments: Vec<CString> = ::std::iter::once(CString::new("rofi").unwrap())
+        .chain(args.into_iter().map(|arg| CString::new(arg).unwrap()))
+        .collect();
+    let mut argv: Vec<*mut c_char> = arguments.iter().map(|arg| arg.as_ptr() as _).collect();
+    let argc = c_int::try_from(argv.len()).unwrap();
+    unsafe { cmd_set_arguments(argc, argv.as_mut_ptr()) };
+    HOST.with(|host| host.borrow_mut().owned_arguments = (arguments, argv));
+}
+
+/// Set the matching configuration used by [`helper::tokenize`](crate::helper::tokenize) on this thread.
+///
+/// As in Rofi, [`case_sensitive`](MatchingConfig::case_sensitive)
+/// and [`case_smart`](MatchingConfig::case_smart) are ignored,
+/// since the case sensitivity is passed to `tokenize` directly.
+/// The default is Rofi's default configuration.
+pub fn set_matching(config: MatchingConfig) {
+    HOST.with(|host| host.borrow_mut().matching = config);
+}
+
+/// Set an option added with [`options::add`](crate::options::add) on this thread,
+/// as if the user had set it in their configuration.
+///
+/// The value is parsed like Rofi does:
+/// numbers with `strtoul` or `strtol`, booleans as `true` or `false`
+/// and characters with [`helper::parse_char`](crate::helper::parse_char).
+/// Returns whether the option exists and the value was valid.
+pub fn set_option(key: &str, value: &str) -> bool {
+    let value = match CString::new(value) {
+        Ok(value) => value,
+        Err(_) => return false,
+    };
+    HOST.with(|host| {
+        let mut host = host.borrow_mut();
+        let option = match host
+            .options
+            .iter_mut()
+            .find(|option| unsafe { CStr::from_ptr(option.key) }.to_bytes() == key.as_bytes())
+        {
+            Some(option) => option,
+            None => return false,
+        };
+        unsafe {
+            match option.ty {
+                XrmOptionType::String => {
+                    glib_sys::g_free(option.mem.cast());
+                    option.mem = glib_sys::g_strchomp(glib_sys::g_strdup(value.as_ptr()));
+                    *option.value.cast::<*mut c_char>() = option.mem;
+                }
+                XrmOptionType::Number => {
+                    *option.value.cast::<c_uint>() =
+                        parse_leading_int(&value.to_string_lossy()) as c_uint;
+                }
+                XrmOptionType::SNumber => {
+                    *option.value.cast::<c_int>() =
+                        parse_leading_int(&value.to_string_lossy()) as c_int;
+                }
+                XrmOptionType::Boolean => {
+                    *option.value.cast::<c_uint>() = match value.to_bytes() {
+                        v if v.eq_ignore_ascii_case(b"true") => 1,
+                        v if v.eq_ignore_ascii_case(b"false") => 0,
+                        _ => return false,
+                    };
+                }
+                XrmOptionType::Char => {
+                    *option.value.cast::<c_char>() = helper_parse_char(value.as_ptr());
+                }
+            }
+        }
+        true
+    })
+}
+
+/// Create the thread pool used by [`pool`](crate::pool), like Rofi does before initializing modes,
+/// with as many threads as [`settings::config`]`.threads`
+/// or the number of processors if that is 0.
+///
+/// Like the configuration, the pool is shared by all threads and is not affected by [`reset`].
+/// Calling this again has no effect.
+/// The completions of [`pool::spawn_with_completion`](crate::pool::spawn_with_completion)
+/// are only called when GLib's default main context is iterated,
+/// for example with `g_main_context_iteration`.
+pub fn init_workers() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let threads = match unsafe { CONFIG.threads } {
+            0 => thread::available_parallelism().map_or(1, |n| n.get().min(128)),
+            threads => threads as usize,
+        };
+        let mut error = ptr::null_mut();
+        unsafe {
+            let pool = glib_sys::g_thread_pool_new(
+                Some(rofi_view_call_thread),
+                ptr::null_mut(),
+                threads as c_int,
+                0,
+                &mut error,
+            );
+            assert!(!pool.is_null(), "failed to create the thread pool");
+            glib_sys::g_thread_pool_set_sort_function(
+                pool,
+                Some(rofi_thread_workers_sort),
+                ptr::null_mut(),
+            );
+            TPOOL = pool;
+        }
+    });
+}
+
+/// Reset all host state on this thread:
+/// recorded calls, command-line arguments, matching configuration and added options.
+pub fn reset() {
+    HOST.with(|host| *host.borrow_mut() = Host::default());
+}
+
+/// A value returned by a mode's `_get_display_value` callback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayValue {
+    /// The text of the entry.
+    pub text: String,
+    /// The state of the entry, as written to the `state` out parameter.
+    pub state: EntryState,
+    /// The Pango attributes appended to the attribute list.
+    ///
+    /// These are `PangoAttribute` pointers;
+    /// they are intentionally leaked because the mock host does not link to Pango.
+    pub attributes: Vec<*mut c_void>,
+}
+
+/// A safe driver for a [`Mode`] running on the mock host.
+///
+/// Each method corresponds to an action Rofi would take on the mode,
+/// and goes through the same `mode_*` functions Rofi uses.
+#[derive(Debug)]
+pub struct Driver {
+    mode: *mut Mode,
+}
+
+impl Driver {
+    /// Create a new driver for a mode.
+    ///
+    /// # Safety
+    ///
+    /// `mode` must be valid for reads and writes for the lifetime of the driver,
+    /// and its callbacks must be sound to call in the way Rofi calls them.
+    pub unsafe fn new(mode: *mut Mode) -> Self {
+        Self { mode }
+    }
+
+    /// Get a pointer to the driven mode.
+    pub fn as_ptr(&self) -> *mut Mode {
+        self.mode
+    }
+
+    /// Initialize the mode, returning whether initialization succeeded.
+    pub fn init(&mut self) -> bool {
+        unsafe { crate::mode_init(self.mode) != 0 }
+    }
+
+    /// Destroy the mode.
+    pub fn destroy(&mut self) {
+        unsafe { crate::mode_destroy(self.mode) };
+    }
+
+    /// Get the number of entries in the mode.
+    pub fn num_entries(&self) -> c_uint {
+        unsafe { crate::mode_get_num_entries(self.mode) }
+    }
+
+    /// Get the display value of a single entry.
+    pub fn display_value(&self, line: c_uint) -> DisplayValue {
+        let mut state = 0;
+        let mut list = ptr::null_mut();
+        let text =
+            unsafe { crate::mode_get_display_value(self.mode, line, &mut state, &mut list, 1) };
+        let text = unsafe { take_string(text) }.unwrap_or_default();
+
+        let mut attributes = Vec::new();
+        let mut node = list;
+        while !node.is_null() {
+            attributes.push(unsafe { (*node).data });
+            node = unsafe { (*node).next };
+        }
+        unsafe { glib_sys::g_list_free(list) };
+
+        DisplayValue {
+            text,
+            state: EntryState::from_bits_retain(state),
+            attributes,
+        }
+    }
+
+    /// Get the display text of every entry in the mode.
+    pub fn entries(&self) -> Vec<String> {
+        (0..self.num_entries())
+            .map(|line| self.display_value(line).text)
+            .collect()
+    }
+
+    /// Get the indices of all the entries that match the given user input.
+    pub fn filter(&self, input: &str, case_sensitive: bool) -> Vec<c_uint> {
+        let input = CString::new(input).unwrap();
+        let tokens =
+            unsafe { crate::helper::tokenize(input.as_ptr(), c_int::from(case_sensitive)) };
+        let matches = (0..self.num_entries())
+            .filter(|&line| unsafe { crate::mode_token_match(self.mode, tokens, line) } != 0)
+            .collect();
+        unsafe { crate::helper::tokenize_free(tokens) };
+        matches
+    }
+
+    /// Act on a user interaction, returning the next mode state
+    /// and the input string as left by the mode.
+    pub fn result(&mut self, menu_retv: c_int, input: &str, line: c_uint) -> (c_int, String) {
+        let input = CString::new(input).unwrap();
+        let mut input = unsafe { glib_sys::g_strdup(input.as_ptr()) };
+        let next = unsafe { crate::mode_result(self.mode, menu_retv, &mut input, line) };
+        let input = unsafe { take_string(input) }.unwrap_or_default();
+        (next, input)
+    }
+
+    /// Get the string used for completion of an entry.
+    pub fn completion(&self, line: c_uint) -> String {
+        let completion = unsafe { crate::mode_get_completion(self.mode, line) };
+        unsafe { take_string(completion as _) }.unwrap_or_default()
+    }
+
+    /// Get the message the mode wants to display, if any.
+    pub fn message(&self) -> Option<String> {
+        unsafe { take_string(crate::mode_get_message(self.mode) as _) }
+    }
+
+    /// Get the display name of the mode.
+    pub fn display_name(&self) -> String {
+        let name = unsafe { crate::mode_get_display_name(self.mode) };
+        unsafe { CStr::from_ptr(name) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Convert a GLib-allocated string to an owned one, freeing the original.
+unsafe fn take_string(s: *mut c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    let owned = unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned();
+    unsafe { glib_sys::g_free(s.cast()) };
+    Some(owned)
+}
+
+// mode.h
+
+#[no_mangle]
+unsafe extern "C" fn mode_init(mode: *mut Mode) -> c_int {
+    let mode = match unsafe { mode.as_mut() } {
+        Some(mode) => mode,
+        None => return 0,
+    };
+    let init = match mode._init {
+        Some(init) => init,
+        None => return 0,
+    };
+    mode.fallback_icon_fetch_uid = 0;
+    mode.fallback_icon_not_found = 0;
+    unsafe { init(mode) }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_destroy(mode: *mut Mode) {
+    let destroy = unsafe { (*mode)._destroy }.expect("mode has no `_destroy`");
+    unsafe { destroy(mode) };
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_get_num_entries(mode: *const Mode) -> c_uint {
+    let get_num_entries =
+        unsafe { (*mode)._get_num_entries }.expect("mode has no `_get_num_entries`");
+    unsafe { get_num_entries(mode) }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_get_display_value(
+    mode: *const Mode,
+    selected_line: c_uint,
+    state: *mut c_int,
+    attribute_list: *mut *mut glib_sys::GList,
+    get_entry: c_int,
+) -> *mut c_char {
+    assert!(!state.is_null());
+    let get_display_value =
+        unsafe { (*mode)._get_display_value }.expect("mode has no `_get_display_value`");
+    unsafe { get_display_value(mode, selected_line, state, attribute_list, get_entry) }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_get_icon(
+    mode: *mut Mode,
+    selected_line: c_uint,
+    height: c_int,
+) -> *mut cairo_sys::cairo_surface_t {
+    match unsafe { (*mode)._get_icon } {
+        Some(get_icon) => unsafe { get_icon(mode, selected_line, height) },
+        None => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_get_completion(
+    mode: *const Mode,
+    selected_line: c_uint,
+) -> *const c_char {
+    if let Some(get_completion) = unsafe { (*mode)._get_completion } {
+        return unsafe { get_completion(mode, selected_line) };
+    }
+    let get_display_value =
+        unsafe { (*mode)._get_display_value }.expect("mode has no `_get_display_value`");
+    let mut state = 0;
+    unsafe { get_display_value(mode, selected_line, &mut state, ptr::null_mut(), 1) }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_result(
+    mode: *mut Mode,
+    menu_retv: c_int,
+    input: *mut *mut c_char,
+    selected_line: c_uint,
+) -> c_int {
+    if menu_retv & menu::NEXT != 0 {
+        return NEXT_DIALOG;
+    }
+    if menu_retv & menu::PREVIOUS != 0 {
+        return PREVIOUS_DIALOG;
+    }
+    if menu_retv & menu::QUICK_SWITCH != 0 {
+        return menu_retv & menu::LOWER_MASK;
+    }
+    let result = unsafe { (*mode)._result }.expect("mode has no `_result`");
+    assert!(!unsafe { *input }.is_null());
+    unsafe { result(mode, menu_retv, input, selected_line) }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_token_match(
+    mode: *const Mode,
+    tokens: *mut *mut RofiIntMatcher,
+    selected_line: c_uint,
+) -> c_int {
+    let token_match = unsafe { (*mode)._token_match }.expect("mode has no `_token_match`");
+    unsafe { token_match(mode, tokens, selected_line) }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_get_name(mode: *const Mode) -> *const c_char {
+    unsafe { (*mode).name }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_free(mode: *mut *mut Mode) {
+    if let Some(free) = unsafe { (**mode).free } {
+        unsafe { free(*mode) };
+    }
+    unsafe { *mode = ptr::null_mut() };
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_get_private_data(mode: *const Mode) -> *mut c_void {
+    unsafe { (*mode).private_data }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_set_private_data(mode: *mut Mode, pd: *mut c_void) {
+    if !pd.is_null() {
+        assert!(
+            unsafe { (*mode).private_data }.is_null(),
+            "private data set on a mode that already has private data",
+        );
+    }
+    unsafe { (*mode).private_data = pd };
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_get_display_name(mode: *const Mode) -> *const c_char {
+    let mode = unsafe { &*mode };
+    if mode.display_name.is_null() {
+        mode.name
+    } else {
+        mode.display_name
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_set_config(_mode: *mut Mode) {}
+
+#[no_mangle]
+unsafe extern "C" fn mode_preprocess_input(mode: *mut Mode, input: *const c_char) -> *const c_char {
+    match unsafe { (*mode)._preprocess_input } {
+        Some(preprocess_input) => unsafe { preprocess_input(mode, input) },
+        None => unsafe { glib_sys::g_strdup(input) },
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_get_message(mode: *const Mode) -> *const c_char {
+    match unsafe { (*mode)._get_message } {
+        Some(get_message) => unsafe { get_message(mode) },
+        None => ptr::null(),
+    }
+}
+
+#[cfg(not(rofi_1_7))]
+#[no_mangle]
+unsafe extern "C" fn mode_create(mode: *const Mode) -> *mut Mode {
+    match unsafe { (*mode)._create } {
+        Some(create) => unsafe { create() },
+        None => ptr::null_mut(),
+    }
+}
+
+#[cfg(not(rofi_1_7))]
+#[no_mangle]
+unsafe extern "C" fn mode_completer_result(
+    mode: *mut Mode,
+    menu_retv: c_int,
+    input: *mut *mut c_char,
+    selected_line: c_uint,
+    path: *mut *mut c_char,
+) -> c_uint {
+    if !unsafe { (*mode).r#type }.contains(crate::ModeType::COMPLETER) {
+        return 0;
+    }
+    match unsafe { (*mode)._completer_result } {
+        Some(completer_result) => unsafe {
+            completer_result(mode, menu_retv, input, selected_line, path)
+        },
+        None => 0,
+    }
+}
+
+#[cfg(not(rofi_1_7))]
+#[no_mangle]
+unsafe extern "C" fn mode_is_completer(mode: *const Mode) -> glib_sys::gboolean {
+    match unsafe { mode.as_ref() } {
+        Some(mode) => mode.r#type.contains(crate::ModeType::COMPLETER).into(),
+        None => glib_sys::GFALSE,
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_get_abi_version(mode: *const Mode) -> c_int {
+    unsafe { (*mode).abi_version as c_int }
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_plugin_set_module(mode: *mut Mode, module: *mut GModule) {
+    unsafe { (*mode).module = module };
+}
+
+#[no_mangle]
+unsafe extern "C" fn mode_plugin_get_module(mode: *mut Mode) -> *mut GModule {
+    unsafe { (*mode).module }
+}
+
+// view.h
+
+#[no_mangle]
+unsafe extern "C" fn rofi_view_reload() {
+    record(Call::Reload);
+}
+
+#[no_mangle]
+unsafe extern "C" fn rofi_view_hide() {
+    record(Call::Hide);
+}
+
+#[export_name = "tpool"]
+static mut TPOOL: *mut glib_sys::GThreadPool = ptr::null_mut();
+
+unsafe extern "C" fn rofi_view_call_thread(
+    data: glib_sys::gpointer,
+    user_data: glib_sys::gpointer,
+) {
+    let state = data.cast::<ThreadState>();
+    if let Some(callback) = unsafe { (*state).callback } {
+        unsafe { callback(state, user_data) };
+    }
+}
+
+unsafe extern "C" fn rofi_thread_workers_sort(
+    a: glib_sys::gconstpointer,
+    b: glib_sys::gconstpointer,
+    _data: glib_sys::gpointer,
+) -> c_int {
+    unsafe { (*a.cast::<ThreadState>()).priority - (*b.cast::<ThreadState>()).priority }
+}
+
+// rofi-icon-fetcher.h
+
+#[no_mangle]
+unsafe extern "C" fn rofi_icon_fetcher_init() {}
+
+#[no_mangle]
+unsafe extern "C" fn rofi_icon_fetcher_destroy() {}
+
+#[no_mangle]
+unsafe extern "C" fn rofi_icon_fetcher_query(name: *const c_char, size: c_int) -> u32 {
+    unsafe { rofi_icon_fetcher_query_advanced(name, size, size) }
+}
+
+#[no_mangle]
+unsafe extern "C" fn rofi_icon_fetcher_query_advanced(
+    name: *const c_char,
+    wsize: c_int,
+    hsize: c_int,
+) -> u32 {
+    let name = unsafe { CStr::from_ptr(name) }
+        .to_string_lossy()
+        .into_owned();
+    record(Call::IconQuery { name, wsize, hsize });
+    HOST.with(|host| {
+        let mut host = host.borrow_mut();
+        host.icon_uid += 1;
+        host.icon_uid
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn rofi_icon_fetcher_get(_uid: u32) -> *mut cairo_sys::cairo_surface_t {
+    ptr::null_mut()
+}
+
+#[no_mangle]
+unsafe extern "C" fn rofi_icon_fetcher_get_ex(
+    _uid: u32,
+    surface: *mut *mut cairo_sys::cairo_surface_t,
+) -> glib_sys::gboolean {
+    unsafe { *surface = ptr::null_mut() };
+    glib_sys::GFALSE
+}
+
+#[no_mangle]
+unsafe extern "C" fn rofi_icon_fetcher_file_is_image(path: *const c_char) -> glib_sys::gboolean {
+    const EXTENSIONS: &[&str] = &[
+        "png", "jpg", "jpeg", "gif", "svg", "xpm", "bmp", "ico", "webp",
+    ];
+    let path = unsafe { CStr::from_ptr(path) }.to_string_lossy();
+    let is_image = Path::new(&*path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| {
+            EXTENSIONS.iter().any(|e| e.eq_ignore_ascii_case(ext))
+        });
+    is_image.into()
+}
+
+// helper.h
+
+/// Get the arguments set by `cmd_set_arguments`.
+///
+/// The returned slice is only valid until the arguments are next changed.
+unsafe fn stored_args<'a>() -> &'a [*mut c_char] {
+    let (argc, argv) = HOST.with(|host| {
+        let host = host.borrow();
+        (host.argc, host.argv)
+    });
+    if argv.is_null() {
+        return &[];
+    }
+    unsafe { ::std::slice::from_raw_parts(argv, argc as usize) }
+}
+
+unsafe fn arg_eq(arg: *const c_char, key: *const c_char) -> bool {
+    unsafe { glib_sys::g_ascii_strcasecmp(arg, key) == 0 }
+}
+
+/// Find the value following `key` on the command line.
+unsafe fn find_arg_value(key: *const c_char) -> Option<*mut c_char> {
+    let args = unsafe { stored_args() };
+    match usize::try_from(unsafe { find_arg(key) }) {
+        Ok(i) if i > 0 && i + 1 < args.len() => Some(args[i + 1]),
+        _ => None,
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn cmd_set_arguments(argc: c_int, argv: *mut *mut c_char) {
+    HOST.with(|host| {
+        let mut host = host.borrow_mut();
+        host.argc = argc;
+        host.argv = argv;
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn find_arg(key: *const c_char) -> c_int {
+    unsafe { stored_args() }
+        .iter()
+        .position(|&arg| unsafe { arg_eq(arg, key) })
+        .map_or(-1, |i| i as c_int)
+}
+
+#[no_mangle]
+unsafe extern "C" fn find_arg_str(key: *const c_char, val: *mut *mut c_char) -> c_int {
+    match unsafe { find_arg_value(key) } {
+        Some(value) if !val.is_null() => {
+            unsafe { *val = value };
+            1
+        }
+        _ => 0,
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn find_arg_strv(key: *const c_char) -> *mut *const c_char {
+    let args = unsafe { stored_args() };
+    let values: Vec<*const c_char> = args
+        .windows(2)
+        .filter(|pair| unsafe { arg_eq(pair[0], key) })
+        .map(|pair| pair[1] as *const c_char)
+        .collect();
+    if values.is_empty() {
+        return ptr::null_mut();
+    }
+    let retv: *mut *const c_char =
+        unsafe { glib_sys::g_malloc0_n(values.len() + 1, mem::size_of::<*const c_char>()) }.cast();
+    for (i, value) in values.into_iter().enumerate() {
+        unsafe { *retv.add(i) = value };
+    }
+    retv
+}
+
+#[no_mangle]
+unsafe extern "C" fn find_arg_int(key: *const c_char, val: *mut c_int) -> c_int {
+    match unsafe { find_arg_value(key) } {
+        Some(value) if !val.is_null() => {
+            let value = unsafe { CStr::from_ptr(value) }.to_string_lossy();
+            unsafe { *val = parse_leading_int(&value) as c_int };
+            1
+        }
+        _ => 0,
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn find_arg_uint(key: *const c_char, val: *mut c_uint) -> c_int {
+    match unsafe { find_arg_value(key) } {
+        Some(value) if !val.is_null() => {
+            let value = unsafe { CStr::from_ptr(value) }.to_string_lossy();
+            unsafe { *val = parse_leading_int(&value) as c_uint };
+            1
+        }
+        _ => 0,
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn find_arg_char(key: *const c_char, val: *mut c_char) -> c_int {
+    match unsafe { find_arg_value(key) } {
+        Some(value) if !val.is_null() => {
+            unsafe { *val = helper_parse_char(value) };
+            1
+        }
+        _ => 0,
+    }
+}
+
+/// Parse a leading decimal integer like `strtol` does, returning 0 if there is none.
+fn parse_leading_int(s: &str) -> i64 {
+    let s = s.trim_start();
+    let digits_start = usize::from(s.starts_with(['-', '+']));
+    let end = s[digits_start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(s.len(), |i| i + digits_start);
+    s[..end].parse().unwrap_or(0)
+}
+
+#[no_mangle]
+unsafe extern "C" fn helper_parse_char(arg: *const c_char) -> c_char {
+    let arg = unsafe { CStr::from_ptr(arg) }.to_bytes();
+    let c = match arg {
+        [c] => *c,
+        [b'\\', c] => match c {
+            b'n' => b'\n',
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b't' => b'\t',
+            b'v' => 0x0B,
+            b'f' => 0x0C,
+            b'r' => b'\r',
+            b'\\' => b'\\',
+            b'0' => b'\0',
+            _ => b'\n',
+        },
+        [b'\\', b'x', hex @ ..] => ::std::str::from_utf8(hex)
+            .ok()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .unwrap_or(0),
+        _ => b'\n',
+    };
+    c as c_char
+}
+
+/// Like Rofi, substitutes `cmd` into `config.run_command` and spawns the result,
+/// returning the read end of its standard output.
+/// Errors are printed to stderr instead of being shown in a dialog.
+#[no_mangle]
+unsafe extern "C" fn execute_generator(cmd: *const c_char) -> c_int {
+    let run_command = unsafe { CStr::from_ptr(CONFIG.run_command) }.to_string_lossy();
+    let cmd = unsafe { CStr::from_ptr(cmd) }.to_string_lossy();
+    let command = match CString::new(run_command.replace("{cmd}", &cmd)) {
+        Ok(command) => command,
+        Err(_) => return -1,
+    };
+    let mut argv = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let mut fd = -1;
+    unsafe {
+        if glib_sys::g_shell_parse_argv(command.as_ptr(), ptr::null_mut(), &mut argv, &mut error)
+            != 0
+        {
+            glib_sys::g_spawn_async_with_pipes(
+                ptr::null(),
+                argv,
+                ptr::null_mut(),
+                glib_sys::G_SPAWN_SEARCH_PATH,
+                None,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                &mut fd,
+                ptr::null_mut(),
+                &mut error,
+            );
+        }
+        if let Some(e) = error.as_ref() {
+            eprintln!(
+                "Failed to execute: '{}'\nError: '{}'",
+                cmd,
+                CStr::from_ptr(e.message).to_string_lossy(),
+            );
+            glib_sys::g_error_free(error);
+            fd = -1;
+        }
+        glib_sys::g_strfreev(argv);
+    }
+    fd
+}
+
+#[no_mangle]
+unsafe extern "C" fn helper_tokenize(
+    input: *const c_char,
+    case_sensitive: c_int,
+) -> *mut *mut RofiIntMatcher {
+    if input.is_null() {
+        return ptr::null_mut();
+    }
+    let input = unsafe { CStr::from_ptr(input) }.to_string_lossy();
+    let config = MatchingConfig {
+        case_sensitive: case_sensitive != 0,
+        case_smart: false,
+        ..HOST.with(|host| host.borrow().matching.clone())
+    };
+    Matchers::new(&input, &config).into_raw()
+}
+
+#[no_mangle]
+unsafe extern "C" fn helper_tokenize_free(tokens: *mut *mut RofiIntMatcher) {
+    if tokens.is_null() {
+        return;
+    }
+    let mut i = 0;
+    loop {
+        let token = unsafe { *tokens.add(i) };
+        if token.is_null() {
+            break;
+        }
+        if !unsafe { (*token).regex }.is_null() {
+            unsafe { glib_sys::g_regex_unref((*token).regex) };
+        }
+        unsafe { glib_sys::g_free(token.cast()) };
+        i += 1;
+    }
+    unsafe { glib_sys::g_free(tokens.cast()) };
+}
+
+#[no_mangle]
+unsafe extern "C" fn helper_token_match(
+    tokens: *const *mut RofiIntMatcher,
+    input: *const c_char,
+) -> c_int {
+    if tokens.is_null() {
+        return 1;
+    }
+    let mut i = 0;
+    loop {
+        let token = unsafe { *tokens.add(i) };
+        if token.is_null() {
+            return 1;
+        }
+        let token = unsafe { &*token };
+        let matched = unsafe { glib_sys::g_regex_match(token.regex, input, 0, ptr::null_mut()) };
+        if (matched != 0) == (token.invert != 0) {
+            return 0;
+        }
+        i += 1;
+    }
+}
+
+#[cfg(feature = "pango")]
+#[no_mangle]
+unsafe extern "C" fn helper_token_match_get_pango_attr(
+    th: crate::RofiHighlightColorStyle,
+    tokens: *mut *mut RofiIntMatcher,
+    input: *const c_char,
+    retv: *mut pango_sys::PangoAttrList,
+) -> *mut pango_sys::PangoAttrList {
+    if tokens.is_null() {
+        return retv;
+    }
+    let mut i = 0;
+    loop {
+        let token = unsafe { *tokens.add(i) };
+        if token.is_null() {
+            return retv;
+        }
+        i += 1;
+        let token = unsafe { &*token };
+        if token.invert != 0 {
+            continue;
+        }
+        let mut info = ptr::null_mut();
+        unsafe {
+            glib_sys::g_regex_match(
+                token.regex,
+                input,
+                glib_sys::G_REGEX_MATCH_PARTIAL,
+                &mut info,
+            );
+            while glib_sys::g_match_info_matches(info) != 0 {
+                let count = glib_sys::g_match_info_get_match_count(info);
+                // Highlight each group when there are any, as fuzzy matching has one per character.
+                for index in (if count > 1 { 1 } else { 0 })..count {
+                    let (mut start, mut end) = (0, 0);
+                    glib_sys::g_match_info_fetch_pos(info, index, &mut start, &mut end);
+                    helper_token_match_set_pango_attr_on_style(retv, start, end, th);
+                }
+                glib_sys::g_match_info_next(info, ptr::null_mut());
+            }
+            glib_sys::g_match_info_free(info);
+        }
+    }
+}
+
+/// Rofi also supports the text transform styles when built against Pango 1.50,
+/// which the mock ignores.
+#[cfg(feature = "pango")]
+#[no_mangle]
+unsafe extern "C" fn helper_token_match_set_pango_attr_on_style(
+    retv: *mut pango_sys::PangoAttrList,
+    start: c_int,
+    end: c_int,
+    th: crate::RofiHighlightColorStyle,
+) {
+    use crate::RofiHighlightStyle as Style;
+
+    let insert = |attr: *mut pango_sys::PangoAttribute| unsafe {
+        (*attr).start_index = start as c_uint;
+        (*attr).end_index = end as c_uint;
+        pango_sys::pango_attr_list_insert(retv, attr);
+    };
+    unsafe {
+        if th.style.contains(Style::BOLD) {
+            insert(pango_sys::pango_attr_weight_new(
+                pango_sys::PANGO_WEIGHT_BOLD,
+            ));
+        }
+        if th.style.contains(Style::UNDERLINE) {
+            insert(pango_sys::pango_attr_underline_new(
+                pango_sys::PANGO_UNDERLINE_SINGLE,
+            ));
+        }
+        if th.style.contains(Style::STRIKETHROUGH) {
+            insert(pango_sys::pango_attr_strikethrough_new(glib_sys::GTRUE));
+        }
+        if th.style.contains(Style::ITALIC) {
+            insert(pango_sys::pango_attr_style_new(
+                pango_sys::PANGO_STYLE_ITALIC,
+            ));
+        }
+        if th.style.contains(Style::COLOR) {
+            let channel = |value: f64| (value * 65535.0) as u16;
+            insert(pango_sys::pango_attr_foreground_new(
+                channel(th.color.red),
+                channel(th.color.green),
+                channel(th.color.blue),
+            ));
+            if th.color.alpha < 1.0 {
+                insert(pango_sys::pango_attr_foreground_alpha_new(channel(
+                    th.color.alpha,
+                )));
+            }
+        }
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn levenshtein(
+    needle: *const c_char,
+    needlelen: c_long,
+    haystack: *const c_char,
+    haystacklen: c_long,
+    case_sensitive: c_int,
+) -> c_uint {
+    let needle = unsafe { char_prefix(needle, needlelen) };
+    let haystack = unsafe { char_prefix(haystack, haystacklen) };
+    sorting::levenshtein(&needle, &haystack, case_sensitive != 0)
+}
+
+#[no_mangle]
+unsafe extern "C" fn rofi_scorer_fuzzy_evaluate(
+    pattern: *const c_char,
+    plen: c_long,
+    s: *const c_char,
+    slen: c_long,
+    case_sensitive: c_int,
+) -> c_int {
+    let pattern = unsafe { char_prefix(pattern, plen) };
+    let s = unsafe { char_prefix(s, slen) };
+    sorting::fuzzy_score(&pattern, &s, case_sensitive != 0)
+}
+
+/// The first `len` characters of a UTF-8 string, as Rofi's functions take lengths in characters.
+///
+/// Like `g_utf8_next_char`, this only reads the bytes of those characters,
+/// so `s` does not need to be NUL-terminated.
+unsafe fn char_prefix(s: *const c_char, len: c_long) -> String {
+    let mut bytes = 0;
+    for _ in 0..len.max(0) {
+        bytes += match unsafe { *s.add(bytes) } as u8 {
+            0xF0..=0xFF => 4,
+            0xE0..=0xEF => 3,
+            0xC0..=0xDF => 2,
+            _ => 1,
+        };
+    }
+    let s = unsafe { ::std::slice::from_raw_parts(s.cast::<u8>(), bytes) };
+    String::from_utf8_lossy(s).into_owned()
+}
+
+#[no_mangle]
+unsafe extern "C" fn parse_ranges(
+    input: *mut c_char,
+    list: *mut *mut RofiRangePair,
+    length: *mut c_uint,
+) {
+    if input.is_null() {
+        return;
+    }
+    let input = unsafe { CStr::from_ptr(input) }.to_string_lossy();
+    for token in input.split(',').filter(|token| !token.is_empty()) {
+        unsafe {
+            *list = glib_sys::g_realloc(
+                (*list).cast(),
+                (*length as usize + 1) * mem::size_of::<RofiRangePair>(),
+            )
+            .cast();
+            *(*list).add(*length as usize) = parse_pair(token);
+            *length += 1;
+        }
+    }
+}
+
+/// Parse a single range like `3`, `3-5`, `3-` or the Python-style `3:5` and `-2:`.
+fn parse_pair(token: &str) -> RofiRangePair {
+    let token = token.trim_start_matches([' ', '\t']);
+    let pythonic = token.contains(':') || token.starts_with('-');
+    let mut parts = token.split(if pythonic { ':' } else { '-' });
+    let start = parse_leading_int(parts.next().unwrap_or("")) as c_int;
+    let mut pair = RofiRangePair { start, stop: start };
+    for part in parts {
+        pair.stop = if part.is_empty() {
+            -1
+        } else {
+            parse_leading_int(part) as c_int - c_int::from(pythonic)
+        };
+    }
+    pair
+}
+
+#[no_mangle]
+unsafe extern "C" fn parse_case_sensitivity(input: *const c_char) -> c_int {
+    let input = unsafe { CStr::from_ptr(input) }.to_string_lossy();
+    // Like Rofi's, this reads the configuration without synchronization.
+    let settings = unsafe { settings::snapshot() };
+    c_int::from(settings.matching.is_case_sensitive(&input))
+}
+
+/// There is no theme or configuration file, so no widget is ever found.
+#[cfg(not(rofi_1_7))]
+#[no_mangle]
+unsafe extern "C" fn rofi_config_find_widget(
+    _name: *const c_char,
+    _state: *const c_char,
+    _exact: glib_sys::gboolean,
+) -> *mut crate::ConfigEntry {
+    ptr::null_mut()
+}
+
+#[cfg(not(rofi_1_7))]
+#[no_mangle]
+unsafe extern "C" fn rofi_theme_find_property(
+    _widget: *mut crate::ConfigEntry,
+    _type: crate::PropertyType,
+    _property: *const c_char,
+    _exact: glib_sys::gboolean,
+) -> *mut crate::Property {
+    ptr::null_mut()
+}
+
+// rofi-types.h
+
+#[export_name = "ProtypeTypeName"]
+static mut PROTYPE_TYPE_NAME: [*const c_char; PropertyType::__Count as usize] = [
+    "Integer\0".as_ptr().cast(),
+    "Double\0".as_ptr().cast(),
+    "String\0".as_ptr().cast(),
+    "Character\0".as_ptr().cast(),
+    "Boolean\0".as_ptr().cast(),
+    "Color\0".as_ptr().cast(),
+    "Image\0".as_ptr().cast(),
+    "Padding\0".as_ptr().cast(),
+    "Reference\0".as_ptr().cast(),
+    "Position\0".as_ptr().cast(),
+    "Highlight\0".as_ptr().cast(),
+    "List\0".as_ptr().cast(),
+    "Orientation\0".as_ptr().cast(),
+    "Cursor\0".as_ptr().cast(),
+    "Inherit\0".as_ptr().cast(),
+];
+
+// xrmoptions.h
+
+#[no_mangle]
+unsafe extern "C" fn config_parser_add_option(
+    ty: XrmOptionType,
+    key: *const c_char,
+    value: *mut *mut c_void,
+    _comment: *const c_char,
+) {
+    let mem = match ty {
+        XrmOptionType::String => unsafe { *value.cast::<*mut c_char>() },
+        _ => ptr::null_mut(),
+    };
+    HOST.with(|host| {
+        host.borrow_mut().options.push(XrmOption {
+            ty,
+            key,
+            value,
+            mem,
+        });
+    });
+}
+
+// settings.h
+
+/// A pointer to a static string, as stored in [`Settings`].
+const fn default_string(s: &'static str) -> *mut c_char {
+    s.as_ptr() as *mut c_char
+}
+
+/// Rofi's default configuration, without a config file or command-line options.
+#[export_name = "config"]
+static mut CONFIG: Settings = Settings {
+    modes: default_string("window,run,ssh\0"),
+    menu_font: default_string("mono 12\0"),
+    show_icons: 0,
+    terminal_emulator: default_string("rofi-sensible-terminal\0"),
+    ssh_client: default_string("ssh\0"),
+    ssh_command: default_string("{terminal} -e {ssh-client} {host} [-p {port}]\0"),
+    run_command: default_string("{cmd}\0"),
+    run_shell_command: default_string("{terminal} -e {cmd}\0"),
+    run_list_command: default_string("\0"),
+    window_command: default_string("wmctrl -i -R {window}\0"),
+    window_match_fields: default_string("all\0"),
+    icon_theme: ptr::null_mut(),
+    location: WindowLocation::CENTER,
+    y_offset: 0,
+    x_offset: 0,
+    fixed_num_lines: 1,
+    disable_history: 0,
+    ignored_prefixes: default_string("\0"),
+    sort: 0,
+    sorting_method_enum: SortingMethod::Normal,
+    sorting_method: default_string("normal\0"),
+    drun_match_fields: default_string("name,generic,exec,categories,keywords\0"),
+    drun_categories: ptr::null_mut(),
+    drun_show_actions: 0,
+    drun_display_format: default_string(
+        "{name} [<span weight='light' size='small'><i>({generic})</i></span>]\0",
+    ),
+    drun_url_launcher: default_string("xdg-open\0"),
+    case_sensitive: 0,
+    #[cfg(not(rofi_1_7))]
+    case_smart: 0,
+    cycle: 1,
+    element_height: 1,
+    sidebar_mode: 0,
+    hover_select: 0,
+    auto_select: 0,
+    parse_hosts: 0,
+    parse_known_hosts: 1,
+    combi_modes: default_string("window,run\0"),
+    matching: default_string("normal\0"),
+    matching_method: MatchingMethod::Normal,
+    tokenize: 1,
+    monitor: default_string("-5\0"),
+    filter: ptr::null_mut(),
+    dpi: -1,
+    threads: 0,
+    scroll_method: 0,
+    window_format: default_string("{w}    {c}   {t}\0"),
+    click_to_exit: 1,
+    theme: ptr::null_mut(),
+    plugin_path: ptr::null_mut(),
+    max_history_size: 25,
+    combi_hide_mode_prefix: 0,
+    combi_display_format: default_string("{mode} {text}\0"),
+    matching_negate_char: b'-' as c_char,
+    cache_dir: ptr::null_mut(),
+    window_thumbnail: 0,
+    drun_use_desktop_cache: 0,
+    drun_reload_desktop_cache: 0,
+    benchmark_ui: 0,
+    normalize_match: 0,
+    steal_focus: 0,
+    application_fallback_icon: ptr::null_mut(),
+    refilter_timeout_limit: 300,
+    xserver_i300_workaround: 0,
+    #[cfg(not(rofi_1_7))]
+    completer_mode: default_string("filebrowser\0"),
+};
@@ -2,8 +2,10 @@
 
 use {
     crate::{mode_private::Mode, types::RofiIntMatcher, GModule},
+    ::bitflags::bitflags,
     ::std::{
         ffi::c_void,
+        fmt::{self, Debug, Formatter},
         os::raw::{c_char, c_int, c_uint},
     },
 };
@@ -11,23 +13,129 @@ use {
 /// ABI version to check if loaded plugin is compatible.
 ///
 /// The current value is 7.
+#[cfg(not(rofi_1_7))]
 pub const ABI_VERSION: c_uint = 7;
 
+/// ABI version to check if loaded plugin is compatible.
+///
+/// The current value is 6.
+#[cfg(rofi_1_7)]
+pub const ABI_VERSION: c_uint = 6;
+
 /// Mode to exit Rofi.
+///
+/// See also [`ModeMode::EXIT`].
 pub const EXIT: c_int = 1000;
 
 /// Mode to skip to the next cycle-able dialog.
+///
+/// See also [`ModeMode::NEXT_DIALOG`].
 pub const NEXT_DIALOG: c_int = 1001;
 
 /// Mode to reload current dialog.
+///
+/// See also [`ModeMode::RELOAD_DIALOG`].
 pub const RELOAD_DIALOG: c_int = 1002;
 
 /// Mode to go to the previous dialog.
+///
+/// See also [`ModeMode::PREVIOUS_DIALOG`].
 pub const PREVIOUS_DIALOG: c_int = 1003;
 
 /// Mode to reload the dialog and unset user input.
+///
+/// See also [`ModeMode::RESET_DIALOG`].
 pub const RESET_DIALOG: c_int = 1004;
 
+/// A value that can be returned from [`ModeResult`](crate::ModeResult):
+/// either one of the dialog actions, or the index of a mode to switch to.
+///
+/// Rofi treats any value below [`EXIT`] as a mode index,
+/// so returning an arbitrary `c_int` can switch to an unexpected mode;
+/// this type only holds values that Rofi gives a meaning to.
+///
+/// ```
+/// use rofi_plugin_sys::{ModeMode, RELOAD_DIALOG};
+///
+/// assert_eq!(ModeMode::new(RELOAD_DIALOG), Some(ModeMode::RELOAD_DIALOG));
+/// assert_eq!(ModeMode::switch_to(2).unwrap().mode_index(), Some(2));
+/// assert_eq!(ModeMode::new(1005), None);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModeMode(c_int);
+
+impl ModeMode {
+    /// Exit Rofi.
+    pub const EXIT: Self = Self(EXIT);
+
+    /// Skip to the next cycle-able dialog.
+    pub const NEXT_DIALOG: Self = Self(NEXT_DIALOG);
+
+    /// Reload the current dialog.
+    pub const RELOAD_DIALOG: Self = Self(RELOAD_DIALOG);
+
+    /// Go to the previous dialog.
+    pub const PREVIOUS_DIALOG: Self = Self(PREVIOUS_DIALOG);
+
+    /// Reload the dialog and unset user input.
+    pub const RESET_DIALOG: Self = Self(RESET_DIALOG);
+
+    /// Validate a raw value returned by a mode.
+    ///
+    /// Returns `None` if Rofi does not give it a meaning.
+    pub const fn new(value: c_int) -> Option<Self> {
+        match value {
+            0..=RESET_DIALOG => Some(Self(value)),
+            _ => None,
+        }
+    }
+
+    /// Switch to the mode with the given index.
+    ///
+    /// Returns `None` if the index is too large to be distinguished from the other values.
+    pub const fn switch_to(index: c_uint) -> Option<Self> {
+        if index < EXIT as c_uint {
+            Some(Self(index as c_int))
+        } else {
+            None
+        }
+    }
+
+    /// The index of the mode to switch to, if this is not one of the dialog actions.
+    pub const fn mode_index(self) -> Option<c_uint> {
+        if self.0 < EXIT {
+            Some(self.0 as c_uint)
+        } else {
+            None
+        }
+    }
+
+    /// Get the raw value to return to Rofi.
+    pub const fn get(self) -> c_int {
+        self.0
+    }
+}
+
+impl Debug for ModeMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::EXIT => f.write_str("EXIT"),
+            Self::NEXT_DIALOG => f.write_str("NEXT_DIALOG"),
+            Self::RELOAD_DIALOG => f.write_str("RELOAD_DIALOG"),
+            Self::PREVIOUS_DIALOG => f.write_str("PREVIOUS_DIALOG"),
+            Self::RESET_DIALOG => f.write_str("RESET_DIALOG"),
+            Self(index) => f.debug_tuple("SwitchTo").field(&index).finish(),
+        }
+    }
+}
+
+impl From<ModeMode> for c_int {
+    fn from(mode: ModeMode) -> Self {
+        mode.get()
+    }
+}
+
 /// States returned by the rofi window.
 pub mod menu {
     use ::std::os::raw::c_int;
@@ -63,7 +171,164 @@ pub mod menu {
     pub const CUSTOM_ACTION: c_int = 0x10000000;
 
     /// Mask
-    pub const LOWER_MASK: c_int = 0x0000FFF;
+    pub const LOWER_MASK: c_int = 0x0000FFFF;
+}
+
+bitflags! {
+    /// The flags of a `menu_retv` value, as the constants in [`menu`].
+    ///
+    /// The bits in [`menu::LOWER_MASK`] are not flags but data,
+    /// and are not part of this type;
+    /// use [`MenuAction`] to decode them along with the flags.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct MenuFlags: c_int {
+        /// Entry is selected.
+        const OK = menu::OK;
+        /// User canceled the operation. (e.g. pressed escape)
+        const CANCEL = menu::CANCEL;
+        /// User requested a mode switch
+        const NEXT = menu::NEXT;
+        /// Custom (non-matched) input was entered.
+        const CUSTOM_INPUT = menu::CUSTOM_INPUT;
+        /// User wanted to delete entry from history.
+        const ENTRY_DELETE = menu::ENTRY_DELETE;
+        /// User wants to jump to another switcher.
+        const QUICK_SWITCH = menu::QUICK_SWITCH;
+        /// User wants to jump to custom command.
+        const CUSTOM_COMMAND = menu::CUSTOM_COMMAND;
+        /// Go to the previous menu.
+        const PREVIOUS = menu::PREVIOUS;
+        /// Go to the complete.
+        const COMPLETE = menu::COMPLETE;
+        /// Bindings specifics
+        const CUSTOM_ACTION = menu::CUSTOM_ACTION;
+    }
+}
+
+impl MenuFlags {
+    /// Get the flags of a `menu_retv` value, ignoring the bits in [`menu::LOWER_MASK`].
+    pub const fn from_retv(menu_retv: c_int) -> Self {
+        Self::from_bits_truncate(menu_retv)
+    }
+}
+
+/// A decoded `menu_retv` value, as passed to [`ModeResult`](crate::ModeResult):
+/// what the user did to end the menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MenuAction {
+    /// The selected entry was accepted (`kb-accept-entry`).
+    ///
+    /// `alt` is set when the alternate binding was used (`kb-accept-alt`, Shift+Return by default).
+    Ok {
+        /// Whether the alternate accept binding was used.
+        alt: bool,
+    },
+    /// The user input was accepted as-is instead of the selected entry (`kb-accept-custom`),
+    /// or no entry matched.
+    ///
+    /// `alt` is set when the alternate binding was used
+    /// (`kb-accept-custom-alt`, Control+Shift+Return by default).
+    CustomInput {
+        /// Whether the alternate accept binding was used.
+        alt: bool,
+    },
+    /// The user asked to delete the selected entry (`kb-delete-entry`).
+    Delete,
+    /// The user canceled the menu (`kb-cancel`).
+    Cancel,
+    /// The user switched to the next mode (`kb-mode-next`).
+    Next,
+    /// The user switched to the previous mode (`kb-mode-previous`).
+    Previous,
+    /// The user asked to complete the selected entry (`kb-mode-complete`).
+    Complete,
+    /// The user switched directly to the mode with the given index.
+    QuickSwitch(u16),
+    /// The user pressed one of the custom key bindings, `kb-custom-N`.
+    ///
+    /// The number is `N`, from 1 to [`MenuAction::MAX_CUSTOM_ACTION`].
+    CustomAction(u16),
+}
+
+impl MenuAction {
+    /// The number of custom key bindings, `kb-custom-1` to `kb-custom-19`.
+    pub const MAX_CUSTOM_ACTION: u16 = 19;
+
+    /// Decode a `menu_retv` value.
+    ///
+    /// Returns `None` if it does not correspond to any action Rofi generates.
+    pub const fn from_retv(menu_retv: c_int) -> Option<Self> {
+        let flags = MenuFlags::from_retv(menu_retv);
+        // Only `u16` bits are in the mask, so this cannot truncate.
+        let data = (menu_retv & menu::LOWER_MASK) as u16;
+        let alt = flags.contains(MenuFlags::CUSTOM_ACTION);
+        Some(if flags.contains(MenuFlags::CANCEL) {
+            Self::Cancel
+        } else if flags.contains(MenuFlags::NEXT) {
+            Self::Next
+        } else if flags.contains(MenuFlags::PREVIOUS) {
+            Self::Previous
+        } else if flags.contains(MenuFlags::QUICK_SWITCH) {
+            Self::QuickSwitch(data)
+        } else if flags.contains(MenuFlags::CUSTOM_COMMAND) {
+            // `kb-custom-N` only goes up to 19, so anything higher means the value is bogus.
+            if data >= Self::MAX_CUSTOM_ACTION {
+                return None;
+            }
+            Self::CustomAction(data + 1)
+        } else if flags.contains(MenuFlags::COMPLETE) {
+            Self::Complete
+        } else if flags.contains(MenuFlags::ENTRY_DELETE) {
+            Self::Delete
+        } else if flags.contains(MenuFlags::CUSTOM_INPUT) {
+            Self::CustomInput { alt }
+        } else if flags.contains(MenuFlags::OK) {
+            Self::Ok { alt }
+        } else {
+            return None;
+        })
+    }
+
+    /// Encode this action as the `menu_retv` value Rofi would pass for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a `CustomAction` whose number is 0
+    /// or greater than [`MAX_CUSTOM_ACTION`](Self::MAX_CUSTOM_ACTION).
+    pub const fn to_retv(self) -> c_int {
+        match self {
+            Self::Ok { alt } => menu::OK | alt_flag(alt),
+            Self::CustomInput { alt } => menu::CUSTOM_INPUT | alt_flag(alt),
+            Self::Delete => menu::ENTRY_DELETE,
+            Self::Cancel => menu::CANCEL,
+            Self::Next => menu::NEXT,
+            Self::Previous => menu::PREVIOUS,
+            Self::Complete => menu::COMPLETE,
+            Self::QuickSwitch(mode) => menu::QUICK_SWITCH | mode as c_int,
+            Self::CustomAction(n) => {
+                assert!(
+                    n != 0 && n <= Self::MAX_CUSTOM_ACTION,
+                    "custom key bindings are numbered from 1 to 19",
+                );
+                menu::CUSTOM_COMMAND | (n - 1) as c_int
+            }
+        }
+    }
+}
+
+const fn alt_flag(alt: bool) -> c_int {
+    if alt {
+        menu::CUSTOM_ACTION
+    } else {
+        0
+    }
+}
+
+impl From<MenuAction> for c_int {
+    fn from(action: MenuAction) -> Self {
+        action.to_retv()
+    }
 }
 
 extern "C" {
@@ -174,6 +439,9 @@ extern "C" {
     pub fn mode_get_message(mode: *const Mode) -> *const c_char;
 
     /// Returns a new instance of the mode.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     pub fn mode_create(mode: *const Mode) -> *mut Mode;
 
     /// Acts on the user interaction. Returns the next mode state.
@@ -183,6 +451,9 @@ extern "C" {
     /// - `input`: Pointer to the user input string. (in, out)
     /// - `selected_line`: the line selected by the user.
     /// - `path`: get the path to the selected file. (out)
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     pub fn mode_completer_result(
         sw: *mut Mode,
         menu_retv: c_int,
@@ -194,6 +465,9 @@ extern "C" {
     /// Check if a mode is a valid completer.
     ///
     /// Returns true if the mode can be used as a completer.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     pub fn mode_is_completer(sw: *const Mode) -> glib_sys::gboolean;
 
     /// Get the mode’s ABI version.
@@ -2,18 +2,23 @@
 
 use {
     crate::{types::RofiIntMatcher, ABI_VERSION},
+    ::bitflags::bitflags,
     ::std::{
         ffi::c_void,
         os::raw::{c_char, c_int, c_uint},
         ptr,
     },
-    bitflags::bitflags,
-    std::mem,
 };
 
+#[cfg(not(rofi_1_7))]
+use std::mem;
+
+#[cfg(not(rofi_1_7))]
 bitflags! {
     /// Indicator what type of mode this is.
     /// For now it can be the classic switcher, or also implement a completer.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
     #[repr(transparent)]
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     pub struct ModeType: c_uint {
@@ -28,6 +33,7 @@ bitflags! {
     }
 }
 
+#[cfg(not(rofi_1_7))]
 #[repr(C)]
 #[allow(dead_code)]
 enum ModeTypeSizeChecker {
@@ -37,6 +43,7 @@ enum ModeTypeSizeChecker {
 }
 
 // Make sure the enum's sizes match
+#[cfg(not(rofi_1_7))]
 const _: [(); mem::size_of::<ModeType>()] = [(); mem::size_of::<ModeTypeSizeChecker>()];
 
 /// Free the switcher.
@@ -44,9 +51,37 @@ const _: [(); mem::size_of::<ModeType>()] = [(); mem::size_of::<ModeTypeSizeChec
 /// Only to be used when the switcher object itself is dynamic and has data in [`Mode::ed`].
 pub type ModeFree = Option<unsafe extern "C" fn(data: *mut Mode)>;
 
+bitflags! {
+    /// The state of an entry,
+    /// as written to the `state` out parameter of [`ModeGetDisplayValue`].
+    ///
+    /// This corresponds to `TextBoxFontType` in `textbox.h`.
+    /// Modes normally only set [`URGENT`](Self::URGENT), [`ACTIVE`](Self::ACTIVE)
+    /// and [`MARKUP`](Self::MARKUP); the rest are used by Rofi itself when drawing.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct EntryState: c_int {
+        /// Text in box is urgent.
+        const URGENT = 1;
+        /// Text in box is active.
+        const ACTIVE = 2;
+        /// Text in box is selected.
+        const SELECTED = 4;
+        /// Text in box has pango markup.
+        const MARKUP = 8;
+        /// Text is on an alternate row
+        const ALT = 16;
+        /// Render font highlighted (inverse colors).
+        const HIGHLIGHT = 32;
+        /// Mask for alternate and highlighted
+        const FMOD_MASK = Self::ALT.bits() | Self::HIGHLIGHT.bits();
+    }
+}
+
 /// Get the string to display for the entry.
 ///
-/// Returns the string and state for displaying.
+/// Returns the string and state for displaying;
+/// see [`EntryState`] for the values of the state.
 ///
 /// - `selected_line`: The selected line
 /// - `state`: The state to display \[out\]
@@ -134,6 +169,9 @@ pub type ModeGetMessage = Option<unsafe extern "C" fn(sw: *const Mode) -> *mut c
 
 /// Create a new instance of this mode.
 /// Free (free) result after use, after using `mode_destroy`.
+///
+/// **Not available with the `rofi-1-7` feature.**
+#[cfg(not(rofi_1_7))]
 pub type ModeCreate = Option<unsafe extern "C" fn() -> *mut Mode>;
 
 /// Handle the user accepting an entry in completion mode.
@@ -143,6 +181,9 @@ pub type ModeCreate = Option<unsafe extern "C" fn() -> *mut Mode>;
 /// - `input`: The input string
 /// - `selected_line`: The selected line
 /// - `path`: the path that was completed
+///
+/// **Not available with the `rofi-1-7` feature.**
+#[cfg(not(rofi_1_7))]
 pub type ModeCompleterResult = Option<
     unsafe extern "C" fn(*mut Mode, c_int, *mut *mut c_char, c_uint, *mut *mut c_char) -> c_uint,
 >;
@@ -205,9 +246,15 @@ pub struct Mode {
     pub free: ModeFree,
 
     /// Create mode.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     pub _create: ModeCreate,
 
     /// If this mode is used as a completer.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     pub _completer_result: ModeCompleterResult,
 
     /// Extra fields for the script.
@@ -223,6 +270,9 @@ pub struct Mode {
     pub fallback_icon_not_found: u32,
 
     /// Type.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     pub r#type: ModeType,
 }
 
@@ -244,12 +294,15 @@ impl Mode {
         _get_message: None,
         private_data: ptr::null_mut(),
         free: None,
+        #[cfg(not(rofi_1_7))]
         _create: None,
+        #[cfg(not(rofi_1_7))]
         _completer_result: None,
         ed: ptr::null_mut(),
         module: ptr::null_mut(),
         fallback_icon_fetch_uid: 0,
         fallback_icon_not_found: 0,
+        #[cfg(not(rofi_1_7))]
         r#type: ModeType::SWITCHER,
     };
 
@@ -0,0 +1,247 @@
+//! Configuration options registered by plugins,
+//! which can be set from the command line and `configuration { }` blocks
+//! and are listed by `rofi -help` and `rofi -dump-config`.
+//!
+//! This corresponds to `xrmoptions.h`.
+//!
+//! Options must be registered before Rofi parses its configuration.
+//! Rofi does that after loading plugins but before initializing any mode,
+//! so options should be registered from the plugin's `g_module_check_init` function,
+//! which GModule calls when Rofi loads the plugin.
+//!
+//! ```no_run
+//! use ::{
+//!     rofi_plugin_sys::{self as rofi, options::{self, ConfigOption}},
+//!     std::{os::raw::c_char, ptr, sync::OnceLock},
+//! };
+//!
+//! static PATH: OnceLock<&'static ConfigOption<Option<String>>> = OnceLock::new();
+//!
+//! #[no_mangle]
+//! pub extern "C" fn g_module_check_init(_: *mut rofi::GModule) -> *const c_char {
+//!     PATH.get_or_init(|| {
+//!         options::add("my-plugin-path", Some("~/notes".to_owned()), "Where my plugin looks")
+//!     });
+//!     ptr::null()
+//! }
+//!
+//! // Later, for example in the mode's `_init`:
+//! let path = PATH.get().unwrap().get();
+//! ```
+
+use ::std::{
+    cell::UnsafeCell,
+    ffi::{c_void, CStr, CString},
+    fmt::{self, Debug, Formatter},
+    os::raw::{c_char, c_int, c_uint},
+    ptr,
+};
+
+/// Type of the config options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub enum XrmOptionType {
+    /// Config option is string.
+    String = 0,
+    /// Config option is an unsigned number.
+    Number = 1,
+    /// Config option is a signed number.
+    SNumber = 2,
+    /// Config option is a boolean (true/false) value.
+    Boolean = 3,
+    /// Config option is a character.
+    Char = 4,
+}
+
+extern "C" {
+    /// Add a config option to the list of available options.
+    ///
+    /// - `type_`: The type of the value.
+    /// - `key`: The key referring to this configuration option,
+    ///   given on the command line with a leading `-`.
+    /// - `value`: The value to update, which also holds the default.
+    ///   Its actual type depends on `type_`:
+    ///   `*mut *mut c_char` for strings, `*mut c_uint` for numbers and booleans,
+    ///   `*mut c_int` for signed numbers and `*mut c_char` for characters.
+    /// - `comment`: Description of this configuration option.
+    ///
+    /// Rofi keeps the `key`, `value` and `comment` pointers,
+    /// so they must live for as long as Rofi runs.
+    /// A default string value must be allocated with GLib,
+    /// since Rofi frees it with `g_free` when the configuration overrides it
+    /// and again when it exits.
+    pub fn config_parser_add_option(
+        type_: XrmOptionType,
+        key: *const c_char,
+        value: *mut *mut c_void,
+        comment: *const c_char,
+    );
+}
+
+/// A type that can be the value of a [`ConfigOption`].
+///
+/// It is implemented for:
+///
+/// - `Option<String>`, for string options.
+///   `None` leaves the option unset by default.
+/// - `u32` and `i32`, for unsigned and signed number options.
+/// - `bool`, for boolean options.
+/// - `char`, for character options.
+///   Rofi stores these as a single byte, so the default must be ASCII
+///   and bytes set from the configuration are interpreted as Latin-1.
+///
+/// This trait is sealed and cannot be implemented outside this crate.
+pub trait OptionValue: private::Sealed {}
+
+mod private {
+    use super::XrmOptionType;
+
+    pub trait Sealed: Sized {
+        /// How the value is stored for Rofi.
+        type Raw: Copy;
+        fn into_raw(self) -> Self::Raw;
+        /// # Safety
+        ///
+        /// `raw` must have been written by Rofi or by `into_raw`.
+        unsafe fn from_raw(raw: Self::Raw) -> Self;
+        const TYPE: XrmOptionType;
+    }
+}
+
+use private::Sealed;
+
+impl OptionValue for Option<String> {}
+impl OptionValue for u32 {}
+impl OptionValue for i32 {}
+impl OptionValue for bool {}
+impl OptionValue for char {}
+
+impl Sealed for Option<String> {
+    type Raw = *mut c_char;
+    fn into_raw(self) -> Self::Raw {
+        match self {
+            Some(s) => {
+                let s = CString::new(s).expect("Rofi option values may not contain NUL bytes");
+                unsafe { glib_sys::g_strdup(s.as_ptr()) }
+            }
+            None => ptr::null_mut(),
+        }
+    }
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        (!raw.is_null()).then(|| {
+            unsafe { CStr::from_ptr(raw) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+    const TYPE: XrmOptionType = XrmOptionType::String;
+}
+
+impl Sealed for u32 {
+    type Raw = c_uint;
+    fn into_raw(self) -> Self::Raw {
+        self
+    }
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        raw
+    }
+    const TYPE: XrmOptionType = XrmOptionType::Number;
+}
+
+impl Sealed for i32 {
+    type Raw = c_int;
+    fn into_raw(self) -> Self::Raw {
+        self
+    }
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        raw
+    }
+    const TYPE: XrmOptionType = XrmOptionType::SNumber;
+}
+
+impl Sealed for bool {
+    type Raw = c_uint;
+    fn into_raw(self) -> Self::Raw {
+        c_uint::from(self)
+    }
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        raw != 0
+    }
+    const TYPE: XrmOptionType = XrmOptionType::Boolean;
+}
+
+impl Sealed for char {
+    type Raw = c_char;
+    fn into_raw(self) -> Self::Raw {
+        assert!(self.is_ascii(), "Rofi character options must be ASCII");
+        self as c_char
+    }
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        char::from(raw as u8)
+    }
+    const TYPE: XrmOptionType = XrmOptionType::Char;
+}
+
+/// The storage of an option registered with [`add`], which Rofi writes to
+/// when it parses its configuration.
+pub struct ConfigOption<T: OptionValue> {
+    key: &'static CStr,
+    value: UnsafeCell<T::Raw>,
+}
+
+// SAFETY: Rofi only writes the value while parsing its configuration,
+// which happens on its main thread before any mode is initialized.
+unsafe impl<T: OptionValue> Sync for ConfigOption<T> {}
+
+impl<T: OptionValue> ConfigOption<T> {
+    /// The key of the option, without the leading `-`.
+    pub fn key(&self) -> &'static str {
+        self.key.to_str().unwrap()
+    }
+
+    /// Get the current value of the option.
+    pub fn get(&self) -> T {
+        unsafe { T::from_raw(*self.value.get()) }
+    }
+}
+
+impl<T: OptionValue + Debug> Debug for ConfigOption<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigOption")
+            .field("key", &self.key())
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+/// Register a configuration option with Rofi.
+///
+/// The option is set with `-key value` on the command line
+/// (or `-key` and `-no-key` for booleans)
+/// and with `key: value;` in a `configuration { }` block.
+/// Its storage, key and comment are leaked, since Rofi keeps pointers to them.
+///
+/// # Panics
+///
+/// Panics if `key`, `comment` or a string `default` contain NUL bytes,
+/// or if a character `default` is not ASCII.
+pub fn add<T: OptionValue>(key: &str, default: T, comment: &str) -> &'static ConfigOption<T> {
+    let leak = |s: &str| -> &'static CStr {
+        let s = CString::new(s).expect("Rofi option keys and comments may not contain NUL bytes");
+        Box::leak(s.into_boxed_c_str())
+    };
+    let option = Box::leak(Box::new(ConfigOption::<T> {
+        key: leak(key),
+        value: UnsafeCell::new(default.into_raw()),
+    }));
+    let comment = leak(comment);
+    unsafe {
+        config_parser_add_option(
+            T::TYPE,
+            option.key.as_ptr(),
+            option.value.get().cast(),
+            comment.as_ptr(),
+        );
+    }
+    option
+}
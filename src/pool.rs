@@ -0,0 +1,155 @@
+//! Running jobs on Rofi's pool of worker threads.
+//!
+//! Rofi filters entries and loads icons on the [`tpool`] thread pool.
+//! Every job pushed to it must start with a [`ThreadState`],
+//! whose `callback` the worker thread calls with the job.
+//! [`spawn`] and [`spawn_with_completion`] take care of this for Rust closures,
+//! so expensive work such as indexing files can be done without blocking Rofi's UI.
+//!
+//! Jobs should be short: when the user types, Rofi waits on its main thread
+//! for filtering jobs queued on the same workers,
+//! so a long job can freeze the UI, especially with `-threads 1`.
+//! Work that may block for a long time belongs on a thread of its own.
+//!
+//! Jobs with a lower priority value are run first, like GLib's priorities.
+//! Rofi discards the jobs that have not started yet when it exits, without running them.
+//!
+//! ```no_run
+//! use rofi_plugin_sys::{glib_sys, pool, view};
+//!
+//! pool::spawn_with_completion(
+//!     glib_sys::G_PRIORITY_LOW,
+//!     || std::fs::read_dir("/usr/share/applications").map(|dir| dir.count()),
+//!     |result| {
+//!         // Back on Rofi's main thread.
+//!         if let Ok(Ok(count)) = result {
+//!             eprintln!("found {count} files");
+//!             unsafe { view::reload() };
+//!         }
+//!     },
+//! )
+//! .unwrap();
+//! ```
+
+use {
+    crate::{tpool, ThreadState},
+    ::std::{
+        error::Error,
+        ffi::{c_void, CStr},
+        fmt::{self, Display, Formatter},
+        os::raw::c_int,
+        panic::{self, AssertUnwindSafe},
+        ptr, thread,
+    },
+};
+
+/// A job as pushed to Rofi's thread pool, with the state Rofi expects first.
+#[repr(C)]
+struct Job<F> {
+    state: ThreadState,
+    f: F,
+}
+
+unsafe extern "C" fn run_job<F: FnOnce() + Send>(state: *mut ThreadState, _: *mut c_void) {
+    let job = unsafe { Box::from_raw(state.cast::<Job<F>>()) };
+    let _ = panic::catch_unwind(AssertUnwindSafe(job.f));
+}
+
+unsafe extern "C" fn free_job<F>(job: *mut c_void) {
+    drop(unsafe { Box::from_raw(job.cast::<Job<F>>()) });
+}
+
+/// An error returned when a job could not be spawned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpawnError {
+    message: String,
+}
+
+impl Display for SpawnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for SpawnError {}
+
+/// Run `f` on one of Rofi's worker threads.
+///
+/// Panics in `f` are caught and discarded, after the panic hook has reported them.
+///
+/// This fails if Rofi's thread pool has not been created yet,
+/// which Rofi does before initializing any mode.
+/// If no new worker thread can be started, an error is also returned,
+/// but the job stays queued and will run when a worker becomes available.
+pub fn spawn<F: FnOnce() + Send + 'static>(priority: c_int, f: F) -> Result<(), SpawnError> {
+    let pool = unsafe { tpool };
+    if pool.is_null() {
+        return Err(SpawnError {
+            message: "Rofi's thread pool has not been created".to_owned(),
+        });
+    }
+    let job = Box::into_raw(Box::new(Job {
+        state: ThreadState {
+            callback: Some(run_job::<F>),
+            free: free_job::<F>,
+            priority,
+        },
+        f,
+    }));
+    let mut error = ptr::null_mut();
+    if unsafe { glib_sys::g_thread_pool_push(pool, job.cast(), &mut error) } != 0 {
+        return Ok(());
+    }
+    let message = match unsafe { error.as_ref() } {
+        Some(error) => unsafe { CStr::from_ptr(error.message) }
+            .to_string_lossy()
+            .into_owned(),
+        None => "failed to start a worker thread".to_owned(),
+    };
+    unsafe { glib_sys::g_clear_error(&mut error) };
+    Err(SpawnError { message })
+}
+
+/// Run `work` on one of Rofi's worker threads,
+/// then call `done` with its result on Rofi's main thread.
+///
+/// `done` is called from an idle source of GLib's default main context,
+/// which is the one Rofi's main loop runs,
+/// so it can safely call the functions provided by Rofi, such as
+/// [`view::reload`](crate::view::reload).
+/// It gets the error that `work` panicked with, if it did.
+/// Panics in `done` itself abort the process, like in any other callback from Rofi.
+///
+/// See [`spawn`] for the errors.
+pub fn spawn_with_completion<T, W, D>(priority: c_int, work: W, done: D) -> Result<(), SpawnError>
+where
+    T: Send + 'static,
+    W: FnOnce() -> T + Send + 'static,
+    D: FnOnce(thread::Result<T>) + Send + 'static,
+{
+    spawn(priority, move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(work));
+        let completion: Box<Completion> = Box::new(Some(Box::new(move || done(result))));
+        unsafe {
+            glib_sys::g_idle_add_full(
+                glib_sys::G_PRIORITY_DEFAULT_IDLE,
+                Some(complete),
+                Box::into_raw(completion).cast(),
+                Some(free_completion),
+            );
+        }
+    })
+}
+
+type Completion = Option<Box<dyn FnOnce() + Send>>;
+
+unsafe extern "C" fn complete(data: glib_sys::gpointer) -> glib_sys::gboolean {
+    if let Some(done) = unsafe { &mut *data.cast::<Completion>() }.take() {
+        done();
+    }
+    glib_sys::G_SOURCE_REMOVE
+}
+
+unsafe extern "C" fn free_completion(data: glib_sys::gpointer) {
+    drop(unsafe { Box::from_raw(data.cast::<Completion>()) });
+}
@@ -0,0 +1,446 @@
+//! Formatting of theme values and sections back into `.rasi` syntax,
+//! like `rofi -dump-theme` does.
+//!
+//! Each function returns a value that implements [`Display`],
+//! so it can be written with `format!`, `write!` or `to_string`.
+//! The output can be parsed by Rofi again,
+//! although values that Rofi normalizes while parsing
+//! (such as colors, which are stored as floating point channels)
+//! may not be written the way they were originally.
+//!
+//! ```
+//! use rofi_plugin_sys::{rasi, ThemeColor, WindowLocation};
+//!
+//! let color = ThemeColor {
+//!     red: 1.0,
+//!     green: 0.5,
+//!     blue: 0.0,
+//!     alpha: 1.0,
+//! };
+//! assert_eq!(rasi::color(&color).to_string(), "rgba(255, 128, 0, 100%)");
+//! assert_eq!(rasi::location(WindowLocation::NORTH_WEST).to_string(), "north west");
+//! ```
+//!
+//! Whole sections, such as those returned by
+//! [`helper::config_find_widget`](crate::helper::config_find_widget),
+//! are written with [`widget`]:
+//!
+//! ```no_run
+//! use {rofi_plugin_sys::{helper, rasi}, std::ptr};
+//!
+//! # #[cfg(not(rofi_1_7))] {
+//! let section = unsafe { helper::config_find_widget(b"my-plugin\0".as_ptr().cast(), ptr::null(), 1) };
+//! if let Some(section) = unsafe { section.as_ref() } {
+//!     eprint!("{}", unsafe { rasi::widget(section) });
+//! }
+//! # }
+//! ```
+
+use {
+    crate::{
+        GListIter, Property, PropertyRef, RofiCursorType, RofiDirection, RofiDistance,
+        RofiDistanceModifier, RofiDistanceUnit, RofiHighlightColorStyle, RofiHighlightStyle,
+        RofiImage, RofiImageType, RofiLineStyle, RofiOrientation, RofiPadding, RofiPixelUnit,
+        RofiScaleType, ThemeColor, ThemeMediaType, ThemeWidget, WindowLocation,
+    },
+    ::std::{
+        ffi::CStr,
+        fmt::{self, Display, Formatter},
+        mem,
+        os::raw::c_char,
+        ptr,
+    },
+};
+
+/// A [`Display`] implementation backed by a closure.
+struct Fmt<F>(F);
+
+impl<F: Fn(&mut Formatter<'_>) -> fmt::Result> Display for Fmt<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// Format a color as `rgba(red, green, blue, alpha%)`.
+pub fn color(color: &ThemeColor) -> impl Display + '_ {
+    Fmt(move |f: &mut Formatter<'_>| {
+        write!(
+            f,
+            "rgba({:.0}, {:.0}, {:.0}, {:.0}%)",
+            color.red * 255.0,
+            color.green * 255.0,
+            color.blue * 255.0,
+            color.alpha * 100.0,
+        )
+    })
+}
+
+/// Format a window location, such as `center` or `north west`.
+pub fn location(location: WindowLocation) -> impl Display {
+    Fmt(move |f: &mut Formatter<'_>| {
+        let sides = [
+            (WindowLocation::NORTH, "north"),
+            (WindowLocation::SOUTH, "south"),
+            (WindowLocation::WEST, "west"),
+            (WindowLocation::EAST, "east"),
+        ];
+        let names = sides
+            .iter()
+            .filter(|&&(side, _)| location.contains(side))
+            .map(|&(_, name)| name)
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            return f.write_str("center");
+        }
+        f.write_str(&names.join(" "))
+    })
+}
+
+/// Format a highlight style, such as `bold italic rgba(255, 0, 0, 100%)`, or `none`.
+pub fn highlight(highlight: &RofiHighlightColorStyle) -> impl Display + '_ {
+    Fmt(move |f: &mut Formatter<'_>| {
+        let styles = [
+            (RofiHighlightStyle::BOLD, "bold"),
+            (RofiHighlightStyle::UNDERLINE, "underline"),
+            (RofiHighlightStyle::STRIKETHROUGH, "strikethrough"),
+            (RofiHighlightStyle::ITALIC, "italic"),
+            (RofiHighlightStyle::UPPERCASE, "uppercase"),
+            (RofiHighlightStyle::LOWERCASE, "lowercase"),
+            (RofiHighlightStyle::CAPITALIZE, "capitalize"),
+        ];
+        let mut words = styles
+            .iter()
+            .filter(|&&(style, _)| highlight.style.contains(style))
+            .map(|&(_, name)| name.to_owned())
+            .collect::<Vec<_>>();
+        if highlight.style.contains(RofiHighlightStyle::COLOR) {
+            words.push(color(&highlight.color).to_string());
+        }
+        if words.is_empty() {
+            return f.write_str("none");
+        }
+        f.write_str(&words.join(" "))
+    })
+}
+
+/// Format a distance, such as `4px`, `1.5em dash` or `calc(100% - 2em)`.
+///
+/// # Safety
+///
+/// The `left` and `right` pointers of every node of the expression tree
+/// must be null or point to valid [`RofiDistanceUnit`]s.
+pub unsafe fn distance(distance: &RofiDistance) -> impl Display + '_ {
+    Fmt(move |f: &mut Formatter<'_>| {
+        let base = &distance.base;
+        match base.mod_type {
+            RofiDistanceModifier::None => unsafe { write_unit(f, base) }?,
+            // Rofi wraps the expression of a `calc()` in a group.
+            RofiDistanceModifier::Group => {
+                f.write_str("calc(")?;
+                unsafe { write_operand(f, base.left) }?;
+                f.write_str(")")?;
+            }
+            _ => {
+                f.write_str("calc(")?;
+                unsafe { write_unit(f, base) }?;
+                f.write_str(")")?;
+            }
+        }
+        match distance.style {
+            RofiLineStyle::Solid => Ok(()),
+            RofiLineStyle::Dash => f.write_str(" dash"),
+        }
+    })
+}
+
+unsafe fn write_unit(f: &mut Formatter<'_>, unit: &RofiDistanceUnit) -> fmt::Result {
+    let operator = match unit.mod_type {
+        RofiDistanceModifier::None => {
+            let suffix = match unit.unit_type {
+                RofiPixelUnit::Px => "px",
+                RofiPixelUnit::Mm => "mm",
+                RofiPixelUnit::Em => "em",
+                RofiPixelUnit::Percent => "%",
+                RofiPixelUnit::Ch => "ch",
+            };
+            return write!(f, "{}{}", unit.distance, suffix);
+        }
+        RofiDistanceModifier::Group => {
+            f.write_str("(")?;
+            unsafe { write_operand(f, unit.left) }?;
+            return f.write_str(")");
+        }
+        RofiDistanceModifier::Add => "+",
+        RofiDistanceModifier::Subtract => "-",
+        RofiDistanceModifier::Divide => "/",
+        RofiDistanceModifier::Multiply => "*",
+        RofiDistanceModifier::Modulo => "modulo",
+        RofiDistanceModifier::Min => "min",
+        RofiDistanceModifier::Max => "max",
+        RofiDistanceModifier::Round => "round",
+        RofiDistanceModifier::Floor => "floor",
+        RofiDistanceModifier::Ceil => "ceil",
+    };
+    unsafe { write_operand(f, unit.left) }?;
+    write!(f, " {} ", operator)?;
+    unsafe { write_operand(f, unit.right) }
+}
+
+unsafe fn write_operand(f: &mut Formatter<'_>, unit: *const RofiDistanceUnit) -> fmt::Result {
+    match unsafe { unit.as_ref() } {
+        Some(unit) => unsafe { write_unit(f, unit) },
+        None => f.write_str("0px"),
+    }
+}
+
+/// Format a padding with as few sides as possible, like CSS:
+/// `4px`, `4px 8px`, `4px 8px 2px` or `4px 8px 2px 1px`.
+///
+/// # Safety
+///
+/// The distances of all sides must be valid, as for [`distance`].
+pub unsafe fn padding(padding: &RofiPadding) -> impl Display + '_ {
+    Fmt(move |f: &mut Formatter<'_>| {
+        let [top, right, bottom, left] = [padding.top, padding.right, padding.bottom, padding.left]
+            .map(|side| unsafe { distance(&side) }.to_string());
+        if top == bottom && left == right {
+            if top == left {
+                f.write_str(&top)
+            } else {
+                write!(f, "{} {}", top, right)
+            }
+        } else if left == right {
+            write!(f, "{} {} {}", top, right, bottom)
+        } else {
+            write!(f, "{} {} {} {}", top, right, bottom, left)
+        }
+    })
+}
+
+/// Format an image, either `url("path")` (with its scaling, if any)
+/// or a `linear-gradient(...)`.
+///
+/// # Safety
+///
+/// The `url` of an URL image must be a valid C string,
+/// and the `colors` of a gradient must be a valid `GList` of [`ThemeColor`]s.
+pub unsafe fn image(image: &RofiImage) -> impl Display + '_ {
+    Fmt(move |f: &mut Formatter<'_>| match image.image_type {
+        RofiImageType::Url => {
+            f.write_str("url(")?;
+            write_string(f, unsafe { cstr(image.url) })?;
+            match image.scaling {
+                RofiScaleType::None => {}
+                RofiScaleType::Both => f.write_str(", both")?,
+                RofiScaleType::Height => f.write_str(", height")?,
+                RofiScaleType::Width => f.write_str(", width")?,
+            }
+            f.write_str(")")
+        }
+        RofiImageType::LinearGradient => {
+            f.write_str("linear-gradient(")?;
+            match image.dir {
+                RofiDirection::Left => f.write_str("to left, ")?,
+                RofiDirection::Right => f.write_str("to right, ")?,
+                RofiDirection::Top => f.write_str("to top, ")?,
+                RofiDirection::Bottom => f.write_str("to bottom, ")?,
+                // Rofi stores angles in turns.
+                RofiDirection::Angle => write!(f, "{}turn, ", image.angle)?,
+            }
+            let colors = unsafe { GListIter::<ThemeColor>::new(image.colors) };
+            for (i, c) in colors.enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", color(c))?;
+            }
+            f.write_str(")")
+        }
+    })
+}
+
+/// Format the value of a property.
+///
+/// Links are written as `var(name)` or `var(name, default)`, without being resolved.
+///
+/// # Safety
+///
+/// `property` must be valid, as for [`PropertyRef::new`].
+pub unsafe fn property_value(property: &Property) -> impl Display + '_ {
+    Fmt(move |f: &mut Formatter<'_>| unsafe { write_value(f, property) })
+}
+
+unsafe fn write_value(f: &mut Formatter<'_>, property: &Property) -> fmt::Result {
+    match unsafe { PropertyRef::new(property) } {
+        PropertyRef::Integer(i) => write!(f, "{}", i),
+        // Debug formatting always includes a decimal point, which makes it a double for Rofi.
+        PropertyRef::Double(d) => write!(f, "{:?}", d),
+        PropertyRef::String(s) => write_string(f, s),
+        PropertyRef::Char(c) => match c as u8 {
+            b'\'' => f.write_str("'\\''"),
+            b'\\' => f.write_str("'\\\\'"),
+            c => write!(f, "'{}'", char::from(c)),
+        },
+        PropertyRef::Boolean(b) => write!(f, "{}", b),
+        PropertyRef::Color(c) => write!(f, "{}", color(&c)),
+        PropertyRef::Image(i) => write!(f, "{}", unsafe { image(i) }),
+        PropertyRef::Padding(p) => write!(f, "{}", unsafe { padding(p) }),
+        PropertyRef::Link { name, default, .. } => {
+            write!(f, "var({}", name.to_string_lossy())?;
+            if let Some(default) = default {
+                f.write_str(", ")?;
+                unsafe { write_value(f, default) }?;
+            }
+            f.write_str(")")
+        }
+        PropertyRef::Position(l) => write!(f, "{}", location(l)),
+        PropertyRef::Highlight(h) => write!(f, "{}", highlight(&h)),
+        PropertyRef::List(items) => {
+            f.write_str("[ ")?;
+            for (i, item) in items.enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                unsafe { write_value(f, item) }?;
+            }
+            f.write_str(" ]")
+        }
+        PropertyRef::Orientation(RofiOrientation::Vertical) => f.write_str("vertical"),
+        PropertyRef::Orientation(RofiOrientation::Horizontal) => f.write_str("horizontal"),
+        PropertyRef::Cursor(RofiCursorType::Default) => f.write_str("default"),
+        PropertyRef::Cursor(RofiCursorType::Pointer) => f.write_str("pointer"),
+        PropertyRef::Cursor(RofiCursorType::Text) => f.write_str("text"),
+        PropertyRef::Inherit => f.write_str("inherit"),
+    }
+}
+
+/// Format a property as `name: value;`.
+///
+/// # Safety
+///
+/// `property` must be valid, as for [`PropertyRef::new`],
+/// and its name must be a valid C string.
+pub unsafe fn property(property: &Property) -> impl Display + '_ {
+    Fmt(move |f: &mut Formatter<'_>| {
+        write!(f, "{}: ", unsafe { cstr(property.name) }.to_string_lossy())?;
+        unsafe { write_value(f, property) }?;
+        f.write_str(";")
+    })
+}
+
+/// Format a widget and all of its children as `.rasi` sections,
+/// with the properties of each section sorted by name.
+///
+/// The selector of each section is built from the names of the widget's ancestors,
+/// like Rofi does: `name state.substate`.
+/// Widgets with a media constraint are written as `@media` blocks,
+/// and widgets without properties only have their children written.
+///
+/// # Safety
+///
+/// `widget` must be a valid widget, as created by Rofi:
+/// its names must be valid C strings,
+/// its `widgets` must point to `num_widgets` valid widgets,
+/// its `media` must be null or valid,
+/// its `properties` must be null or a valid `GHashTable` of [`Property`]s keyed by name,
+/// and the same must hold for its parents and children.
+pub unsafe fn widget(widget: &ThemeWidget) -> impl Display + '_ {
+    Fmt(move |f: &mut Formatter<'_>| unsafe { write_widget(f, widget, 0) })
+}
+
+unsafe fn write_widget(f: &mut Formatter<'_>, widget: &ThemeWidget, indent: usize) -> fmt::Result {
+    let children = (0..widget.num_widgets as usize).map(|i| unsafe { &**widget.widgets.add(i) });
+
+    if let Some(media) = unsafe { widget.media.as_ref() } {
+        let feature = match media.r#type {
+            ThemeMediaType::MinWidth => "min-width",
+            ThemeMediaType::MaxWidth => "max-width",
+            ThemeMediaType::MinHeight => "min-height",
+            ThemeMediaType::MaxHeight => "max-height",
+            ThemeMediaType::MonId => "monitor-id",
+            ThemeMediaType::MinAspectRatio => "min-aspect-ratio",
+            ThemeMediaType::MaxAspectRatio => "max-aspect-ratio",
+            // The name of the environment variable is only kept in the widget's name.
+            ThemeMediaType::Boolean | ThemeMediaType::Invalid => "",
+        };
+        if feature.is_empty() {
+            writeln!(
+                f,
+                "{:indent$}{} {{",
+                "",
+                unsafe { cstr(widget.name) }.to_string_lossy()
+            )?;
+        } else {
+            writeln!(
+                f,
+                "{:indent$}@media ( {}: {} ) {{",
+                "", feature, media.value
+            )?;
+        }
+        for child in children {
+            unsafe { write_widget(f, child, indent + 4) }?;
+        }
+        return writeln!(f, "{:indent$}}}", "");
+    }
+
+    if !widget.properties.is_null() {
+        let mut names = Vec::new();
+        let mut ancestor = Some(widget);
+        while let Some(w) = ancestor {
+            let name = unsafe { cstr(w.name) };
+            if name.to_bytes() == b"Root" || !w.media.is_null() {
+                break;
+            }
+            names.push(name.to_string_lossy());
+            ancestor = unsafe { w.parent.as_ref() };
+        }
+        names.reverse();
+        let selector = match names.split_first() {
+            Some((first, states)) if !states.is_empty() => {
+                format!("{} {}", first, states.join("."))
+            }
+            Some((first, _)) => first.to_string(),
+            None => "*".to_owned(),
+        };
+        writeln!(f, "{:indent$}{} {{", "", selector)?;
+
+        let mut properties = Vec::new();
+        let mut iter = unsafe { mem::zeroed::<glib_sys::GHashTableIter>() };
+        unsafe { glib_sys::g_hash_table_iter_init(&mut iter, widget.properties) };
+        let mut value = ptr::null_mut();
+        while unsafe { glib_sys::g_hash_table_iter_next(&mut iter, ptr::null_mut(), &mut value) }
+            != 0
+        {
+            properties.push(unsafe { &*value.cast::<Property>() });
+        }
+        properties.sort_by_key(|p| unsafe { cstr(p.name) });
+        for p in properties {
+            writeln!(f, "{:indent$}    {}", "", unsafe { property(p) })?;
+        }
+        writeln!(f, "{:indent$}}}", "")?;
+    }
+
+    for child in children {
+        unsafe { write_widget(f, child, indent) }?;
+    }
+    Ok(())
+}
+
+/// Write a string in double quotes, escaping quotes and backslashes.
+fn write_string(f: &mut Formatter<'_>, s: &CStr) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.to_string_lossy().chars() {
+        if c == '"' || c == '\\' {
+            f.write_str("\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    f.write_str("\"")
+}
+
+unsafe fn cstr<'a>(s: *const c_char) -> &'a CStr {
+    if s.is_null() {
+        return Default::default();
+    }
+    unsafe { CStr::from_ptr(s) }
+}
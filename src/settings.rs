@@ -0,0 +1,336 @@
+//! Rofi's configuration, as set by the user through the command line and config file.
+//!
+//! This corresponds to `settings.h`.
+//!
+//! The raw [`config`] global can be read directly,
+//! but [`snapshot`] is usually more convenient:
+//! it copies the options that are relevant to plugins into an owned [`Snapshot`].
+//!
+//! ```no_run
+//! use rofi_plugin_sys::settings;
+//!
+//! // SAFETY: This is called from Rofi's main thread.
+//! let settings = unsafe { settings::snapshot() };
+//! if settings.show_icons {
+//!     // load icons...
+//! }
+//! let case_sensitive = settings.matching.is_case_sensitive("input");
+//! ```
+
+use {
+    crate::{matching, sorting, WindowLocation},
+    ::std::{
+        ffi::CStr,
+        os::raw::{c_char, c_int, c_uint},
+        ptr,
+    },
+};
+
+/// Possible methods of matching, as stored in [`Settings::matching_method`].
+///
+/// See [`matching::MatchingMethod`] for a description of each method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub enum MatchingMethod {
+    /// `-matching normal`, the default.
+    Normal = 0,
+    /// `-matching regex`.
+    Regex = 1,
+    /// `-matching glob`.
+    Glob = 2,
+    /// `-matching fuzzy`.
+    Fuzzy = 3,
+    /// `-matching prefix`.
+    Prefix = 4,
+}
+
+impl From<MatchingMethod> for matching::MatchingMethod {
+    fn from(method: MatchingMethod) -> Self {
+        match method {
+            MatchingMethod::Normal => Self::Normal,
+            MatchingMethod::Regex => Self::Regex,
+            MatchingMethod::Glob => Self::Glob,
+            MatchingMethod::Fuzzy => Self::Fuzzy,
+            MatchingMethod::Prefix => Self::Prefix,
+        }
+    }
+}
+
+/// Possible sorting methods, as stored in [`Settings::sorting_method_enum`].
+///
+/// See [`sorting::SortingMethod`] for a description of each method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub enum SortingMethod {
+    /// `-sorting-method normal`, the default.
+    Normal = 0,
+    /// `-sorting-method fzf`.
+    Fzf = 1,
+}
+
+impl From<SortingMethod> for sorting::SortingMethod {
+    fn from(method: SortingMethod) -> Self {
+        match method {
+            SortingMethod::Normal => Self::Normal,
+            SortingMethod::Fzf => Self::Fzf,
+        }
+    }
+}
+
+/// Settings structure holding all (static) configurable options.
+///
+/// Strings are owned by Rofi and may be null if the option is unset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Settings {
+    /// List of enabled modes.
+    pub modes: *mut c_char,
+    /// Font string (pango format).
+    pub menu_font: *mut c_char,
+
+    /// Whether to load and show icons.
+    pub show_icons: glib_sys::gboolean,
+
+    /// Terminal to use.
+    pub terminal_emulator: *mut c_char,
+    /// SSH client to use.
+    pub ssh_client: *mut c_char,
+    /// Command to execute when ssh session is selected.
+    pub ssh_command: *mut c_char,
+    /// Command for executing an application.
+    pub run_command: *mut c_char,
+    /// Command for executing an application in a terminal.
+    pub run_shell_command: *mut c_char,
+    /// Command for listing executables.
+    pub run_list_command: *mut c_char,
+    /// Command for window.
+    pub window_command: *mut c_char,
+    /// Window fields to match in window mode.
+    pub window_match_fields: *mut c_char,
+    /// Theme for icons.
+    pub icon_theme: *mut c_char,
+
+    /// Windows location/gravity.
+    pub location: WindowLocation,
+    /// Y offset.
+    pub y_offset: c_int,
+    /// X offset.
+    pub x_offset: c_int,
+    /// Always show the configured number of lines, even if less lines are available.
+    pub fixed_num_lines: c_uint,
+    /// Do not use history.
+    pub disable_history: c_uint,
+    /// Programs ignored for history.
+    pub ignored_prefixes: *mut c_char,
+    /// Toggle to enable sorting.
+    pub sort: c_uint,
+    /// Sorting method.
+    pub sorting_method_enum: SortingMethod,
+    /// Sorting method, as given by the user.
+    pub sorting_method: *mut c_char,
+    /// Desktop entries to match in drun.
+    pub drun_match_fields: *mut c_char,
+    /// Only show entries in this category.
+    pub drun_categories: *mut c_char,
+    /// Desktop entry show actions.
+    pub drun_show_actions: c_uint,
+    /// Desktop format display.
+    pub drun_display_format: *mut c_char,
+    /// Desktop Link launch command.
+    pub drun_url_launcher: *mut c_char,
+    /// Search case sensitivity.
+    pub case_sensitive: c_uint,
+    /// Smart case sensitivity like vim.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
+    pub case_smart: c_uint,
+    /// Cycle through in the element list.
+    pub cycle: c_uint,
+    /// Height of an element in number of rows.
+    pub element_height: c_int,
+    /// Sidebar mode, show the modes.
+    pub sidebar_mode: c_uint,
+    /// Mouse hover automatically selects.
+    pub hover_select: glib_sys::gboolean,
+    /// Auto select.
+    pub auto_select: c_uint,
+    /// Hosts file parsing.
+    pub parse_hosts: c_uint,
+    /// Known_hosts file parsing.
+    pub parse_known_hosts: c_uint,
+    /// Modes to combine into one view.
+    pub combi_modes: *mut c_char,
+    /// Matching method, as given by the user.
+    pub matching: *mut c_char,
+    /// Matching method.
+    pub matching_method: MatchingMethod,
+    /// Tokenize the input.
+    pub tokenize: c_uint,
+    /// Monitors.
+    pub monitor: *mut c_char,
+    /// Filter.
+    pub filter: *mut c_char,
+    /// DPI.
+    pub dpi: c_int,
+    /// Number of threads (1 to disable).
+    pub threads: c_uint,
+    /// Scroll method.
+    pub scroll_method: c_uint,
+
+    /// Window format.
+    pub window_format: *mut c_char,
+    /// Click outside the window to exit.
+    pub click_to_exit: c_int,
+
+    /// Theme file.
+    pub theme: *mut c_char,
+    /// Path where plugins can be found.
+    pub plugin_path: *mut c_char,
+
+    /// Maximum history length per mode.
+    pub max_history_size: c_uint,
+    /// Hide the mode prefix in combi mode.
+    pub combi_hide_mode_prefix: glib_sys::gboolean,
+    /// Format of the mode prefix in combi mode.
+    pub combi_display_format: *mut c_char,
+
+    /// Character that negates a token, or NUL if negation is disabled.
+    pub matching_negate_char: c_char,
+
+    /// Cache directory.
+    pub cache_dir: *mut c_char,
+
+    /// Window thumbnails.
+    pub window_thumbnail: glib_sys::gboolean,
+
+    /// Use the drun cache.
+    pub drun_use_desktop_cache: glib_sys::gboolean,
+    /// Rebuild the drun cache.
+    pub drun_reload_desktop_cache: glib_sys::gboolean,
+
+    /// Benchmark.
+    pub benchmark_ui: glib_sys::gboolean,
+
+    /// Normalize the string before matching.
+    pub normalize_match: glib_sys::gboolean,
+    /// Steal focus.
+    pub steal_focus: glib_sys::gboolean,
+    /// Fallback icon.
+    pub application_fallback_icon: *mut c_char,
+
+    /// Refilter timeout in milliseconds.
+    pub refilter_timeout_limit: c_uint,
+
+    /// Workaround for broken xserver (#300 on xserver, #611).
+    pub xserver_i300_workaround: glib_sys::gboolean,
+    /// What mode to use for completion.
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
+    pub completer_mode: *mut c_char,
+}
+
+extern "C" {
+    /// Global instance of the configuration structure.
+    ///
+    /// Rofi fills this in at startup, before loading plugins,
+    /// and only changes it afterwards from its main thread
+    /// (for example when the user switches the matching method).
+    pub static mut config: Settings;
+}
+
+/// An owned copy of the options in [`Settings`] that are most relevant to plugins.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Snapshot {
+    /// The enabled modes (`-modes`).
+    pub modes: Option<String>,
+    /// Whether icons are shown (`-show-icons`).
+    /// Plugins can skip loading icons when this is false.
+    pub show_icons: bool,
+    /// The icon theme (`-icon-theme`).
+    pub icon_theme: Option<String>,
+    /// The fallback icon for applications (`-application-fallback-icon`).
+    pub application_fallback_icon: Option<String>,
+    /// The terminal emulator (`-terminal`).
+    pub terminal: Option<String>,
+    /// The command used to run a program (`-run-command`),
+    /// where `{cmd}` is replaced by the program.
+    pub run_command: Option<String>,
+    /// The command used to run a program in a terminal (`-run-shell-command`),
+    /// where `{terminal}` and `{cmd}` are replaced.
+    pub run_shell_command: Option<String>,
+    /// How user input is matched against entries,
+    /// including case sensitivity (`-matching`, `-tokenize`, `-matching-negate-char`,
+    /// `-case-sensitive` and `-case-smart`).
+    pub matching: matching::MatchingConfig,
+    /// Whether entries are sorted by relevance (`-sort`).
+    pub sort: bool,
+    /// How entries are sorted when sorting is enabled (`-sorting-method`).
+    pub sorting_method: sorting::SortingMethod,
+    /// The number of threads to use, where 0 means autodetect and 1 disables threading
+    /// (`-threads`).
+    pub threads: u32,
+    /// The cache directory (`-cache-dir`).
+    pub cache_dir: Option<String>,
+}
+
+impl Snapshot {
+    /// Copy the options out of a [`Settings`].
+    ///
+    /// # Safety
+    ///
+    /// All the string fields of `settings` must be null or point to valid C strings.
+    pub unsafe fn from_raw(settings: &Settings) -> Self {
+        let string = |s: *mut c_char| unsafe { owned_string(s) };
+
+        #[cfg(not(rofi_1_7))]
+        let case_smart = settings.case_smart != 0;
+        #[cfg(rofi_1_7)]
+        let case_smart = false;
+
+        Self {
+            modes: string(settings.modes),
+            show_icons: settings.show_icons != 0,
+            icon_theme: string(settings.icon_theme),
+            application_fallback_icon: string(settings.application_fallback_icon),
+            terminal: string(settings.terminal_emulator),
+            run_command: string(settings.run_command),
+            run_shell_command: string(settings.run_shell_command),
+            matching: matching::MatchingConfig {
+                method: settings.matching_method.into(),
+                tokenize: settings.tokenize != 0,
+                negate_char: match settings.matching_negate_char as u8 {
+                    0 => None,
+                    c => Some(char::from(c)),
+                },
+                case_sensitive: settings.case_sensitive != 0,
+                case_smart,
+            },
+            sort: settings.sort != 0,
+            sorting_method: settings.sorting_method_enum.into(),
+            threads: settings.threads,
+            cache_dir: string(settings.cache_dir),
+        }
+    }
+}
+
+/// Take a [`Snapshot`] of Rofi's current [`config`].
+///
+/// # Safety
+///
+/// This must be called from Rofi's main thread, for example from a mode's `_init`,
+/// and not from a worker thread such as a [`pool`](crate::pool) job,
+/// since Rofi may write to [`config`] from its main thread at the same time.
+/// Take the snapshot beforehand and move it into the job instead.
+pub unsafe fn snapshot() -> Snapshot {
+    unsafe { Snapshot::from_raw(&*ptr::addr_of!(config)) }
+}
+
+unsafe fn owned_string(s: *const c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    Some(unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned())
+}
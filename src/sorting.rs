@@ -0,0 +1,239 @@
+//! A port of the scoring functions Rofi uses to sort entries,
+//! for sorting entries by relevance before Rofi sees them.
+//!
+//! [`fuzzy_score`] and [`levenshtein`] give the same results as
+//! [`helper::scorer_fuzzy_evaluate`](crate::helper::scorer_fuzzy_evaluate) and
+//! [`helper::levenshtein`](crate::helper::levenshtein),
+//! but are implemented in Rust over `&str`,
+//! so they do not need Rofi and can be called from any thread.
+//! Characters are classified and case-folded with GLib's Unicode functions, like in Rofi.
+//!
+//! ```
+//! use rofi_plugin_sys::sorting::{levenshtein, rank, SortingMethod};
+//!
+//! assert_eq!(levenshtein("kitten", "Sitting", false), 3);
+//!
+//! let entries = ["Settings", "set-theme", "reset"];
+//! assert_eq!(rank(&entries, "st", SortingMethod::Fzf, false), [1, 0, 2]);
+//! ```
+
+use ::std::{cmp, os::raw::c_int};
+
+/// A Unicode code point, as GLib's `gunichar`.
+type Gunichar = u32;
+
+/// The score of a gap at the start of the string, per character.
+pub const LEADING_GAP_SCORE: c_int = -4;
+/// The score of a gap between two matched characters, per character.
+pub const GAP_SCORE: c_int = -5;
+/// The score of a match at the start of a word.
+pub const WORD_START_SCORE: c_int = 50;
+/// The score of a match on a non-word character.
+pub const NON_WORD_SCORE: c_int = 40;
+/// The score of a match at the start of a subword in CamelCase or camel123 words.
+pub const CAMEL_SCORE: c_int = WORD_START_SCORE + GAP_SCORE - 1;
+/// The score of a match directly after the previous match.
+pub const CONSECUTIVE_SCORE: c_int = WORD_START_SCORE + GAP_SCORE;
+/// The multiplier for matches of characters that do not start a word in the pattern.
+pub const PATTERN_NON_START_MULTIPLIER: c_int = 1;
+/// The multiplier for matches of characters that start a word in the pattern.
+pub const PATTERN_START_MULTIPLIER: c_int = 2;
+/// Strings with more characters than this are not scored.
+pub const FUZZY_SCORER_MAX_LENGTH: usize = 256;
+
+const MIN_SCORE: c_int = c_int::MIN / 2;
+
+/// The method used to sort entries, corresponding to Rofi's `-sorting-method` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortingMethod {
+    /// Sort by the [`levenshtein`] distance to the pattern.
+    #[default]
+    Normal,
+    /// Sort by the [`fuzzy_score`] of the pattern.
+    Fzf,
+}
+
+impl SortingMethod {
+    /// Parse the name used for the method in Rofi's `-sorting-method` option.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "normal" | "levenshtein" => Self::Normal,
+            "fzf" => Self::Fzf,
+            _ => return None,
+        })
+    }
+
+    /// Get the sorting key of an entry, as computed by Rofi: lower is better.
+    pub fn distance(self, pattern: &str, entry: &str, case_sensitive: bool) -> i64 {
+        match self {
+            Self::Normal => i64::from(levenshtein(pattern, entry, case_sensitive)),
+            Self::Fzf => i64::from(fuzzy_score(pattern, entry, case_sensitive)),
+        }
+    }
+}
+
+/// Sort the indices of `entries` from most to least relevant to `pattern`,
+/// the way Rofi does when sorting is enabled.
+///
+/// Entries with the same distance keep their relative order.
+pub fn rank<S: AsRef<str>>(
+    entries: &[S],
+    pattern: &str,
+    method: SortingMethod,
+    case_sensitive: bool,
+) -> Vec<usize> {
+    let mut keyed: Vec<(i64, usize)> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| (method.distance(pattern, entry.as_ref(), case_sensitive), i))
+        .collect();
+    keyed.sort_by_key(|&(distance, _)| distance);
+    keyed.into_iter().map(|(_, i)| i).collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Lower,
+    Upper,
+    Digit,
+    NonWord,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        let c = Gunichar::from(c);
+        unsafe {
+            if glib_sys::g_unichar_islower(c) != 0 {
+                Self::Lower
+            } else if glib_sys::g_unichar_isupper(c) != 0 {
+                Self::Upper
+            } else if glib_sys::g_unichar_isdigit(c) != 0 {
+                Self::Digit
+            } else {
+                Self::NonWord
+            }
+        }
+    }
+
+    fn score(prev: Self, cur: Self) -> c_int {
+        if prev == Self::NonWord && cur != Self::NonWord {
+            WORD_START_SCORE
+        } else if (prev == Self::Lower && cur == Self::Upper)
+            || (prev != Self::Digit && cur == Self::Digit)
+        {
+            CAMEL_SCORE
+        } else if cur == Self::NonWord {
+            NON_WORD_SCORE
+        } else {
+            0
+        }
+    }
+}
+
+fn to_lower(c: char) -> Gunichar {
+    unsafe { glib_sys::g_unichar_tolower(Gunichar::from(c)) }
+}
+
+fn chars_eq(a: char, b: char, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        a == b
+    } else {
+        to_lower(a) == to_lower(b)
+    }
+}
+
+/// Run Rofi's global sequence alignment algorithm to score how well `pattern` matches `s`,
+/// like [`helper::scorer_fuzzy_evaluate`](crate::helper::scorer_fuzzy_evaluate),
+/// whose documentation describes the scoring criteria.
+///
+/// Returns the sorting weight: lower is better.
+pub fn fuzzy_score(pattern: &str, s: &str, case_sensitive: bool) -> c_int {
+    let s: Vec<char> = s.chars().collect();
+    if s.len() > FUZZY_SCORER_MAX_LENGTH {
+        return -MIN_SCORE;
+    }
+
+    let mut prev = CharClass::NonWord;
+    let score: Vec<c_int> = s
+        .iter()
+        .map(|&c| {
+            let cur = CharClass::of(c);
+            let score = CharClass::score(prev, cur);
+            prev = cur;
+            score
+        })
+        .collect();
+    // dp[si]: maximum value by aligning pattern[0..pi] to s[0..si]
+    let mut dp = vec![MIN_SCORE; s.len()];
+
+    // whether we are aligning the first character of pattern
+    let mut pfirst = true;
+    // whether the start of a word in pattern
+    let mut pstart = true;
+    // uleft: value of the upper left cell;
+    // ulefts: maximum value of uleft and cells on the left.
+    let (mut uleft, mut ulefts) = (0, 0);
+    for pc in pattern.chars() {
+        if unsafe { glib_sys::g_unichar_isspace(Gunichar::from(pc)) } != 0 {
+            pstart = true;
+            continue;
+        }
+        let mut lefts = MIN_SCORE;
+        for (si, &sc) in s.iter().enumerate() {
+            let left = dp[si];
+            lefts = cmp::max(lefts + GAP_SCORE, left);
+            dp[si] = if chars_eq(pc, sc, case_sensitive) {
+                let multiplier = if pstart {
+                    PATTERN_START_MULTIPLIER
+                } else {
+                    PATTERN_NON_START_MULTIPLIER
+                };
+                let t = score[si] * multiplier;
+                if pfirst {
+                    LEADING_GAP_SCORE * si as c_int + t
+                } else {
+                    cmp::max(uleft + CONSECUTIVE_SCORE, ulefts + t)
+                }
+            } else {
+                MIN_SCORE
+            };
+            uleft = left;
+            ulefts = lefts;
+        }
+        pfirst = false;
+        pstart = false;
+    }
+
+    let lefts = dp
+        .iter()
+        .fold(MIN_SCORE, |lefts, &d| cmp::max(lefts + GAP_SCORE, d));
+    -lefts
+}
+
+/// UTF-8 aware Levenshtein distance between `needle` and `haystack`,
+/// like [`helper::levenshtein`](crate::helper::levenshtein).
+pub fn levenshtein(needle: &str, haystack: &str, case_sensitive: bool) -> u32 {
+    let fold = |c: char| {
+        if case_sensitive {
+            Gunichar::from(c)
+        } else {
+            to_lower(c)
+        }
+    };
+    let needle: Vec<Gunichar> = needle.chars().map(fold).collect();
+    let mut column: Vec<u32> = (0..=needle.len() as u32).collect();
+    for (x, haystackc) in haystack.chars().map(fold).enumerate() {
+        let mut lastdiag = x as u32;
+        column[0] = x as u32 + 1;
+        for (y, &needlec) in needle.iter().enumerate() {
+            let y = y + 1;
+            let olddiag = column[y];
+            column[y] = cmp::min(
+                cmp::min(column[y] + 1, column[y - 1] + 1),
+                lastdiag + u32::from(needlec != haystackc),
+            );
+            lastdiag = olddiag;
+        }
+    }
+    column[needle.len()]
+}
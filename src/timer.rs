@@ -0,0 +1,165 @@
+//! Periodic refreshes for modes whose entries change while Rofi is open,
+//! such as process lists.
+//!
+//! A [`Timer`] calls a callback on Rofi's main thread at a fixed interval
+//! with the mode's private data, as set with [`mode_set_private_data`],
+//! and reloads the view when the callback reports that something changed.
+//! The timer is stopped when it is dropped,
+//! so storing it in the private data stops it when the mode's `_destroy` frees the data.
+//! It also stops by itself once the mode's private data is null.
+//!
+//! ```no_run
+//! use {
+//!     rofi_plugin_sys::{self as rofi, timer::Timer},
+//!     std::{os::raw::c_int, time::Duration},
+//! };
+//!
+//! struct State {
+//!     processes: Vec<String>,
+//!     timer: Option<Timer>,
+//! }
+//!
+//! fn list_processes() -> Vec<String> {
+//!     // ...
+//! #   Vec::new()
+//! }
+//!
+//! unsafe extern "C" fn init(mode: *mut rofi::Mode) -> c_int {
+//!     let state = Box::new(State { processes: list_processes(), timer: None });
+//!     unsafe { rofi::mode_set_private_data(mode, Box::into_raw(state).cast()) };
+//!     let timer = unsafe {
+//!         Timer::start(mode, Duration::from_secs(1), |state: &mut State| {
+//!             let processes = list_processes();
+//!             let changed = processes != state.processes;
+//!             state.processes = processes;
+//!             changed
+//!         })
+//!     };
+//!     let state = unsafe { &mut *rofi::mode_get_private_data(mode).cast::<State>() };
+//!     state.timer = Some(timer);
+//!     1
+//! }
+//!
+//! unsafe extern "C" fn destroy(mode: *mut rofi::Mode) {
+//!     let state = unsafe { rofi::mode_get_private_data(mode) }.cast::<State>();
+//!     if !state.is_null() {
+//!         unsafe { rofi::mode_set_private_data(mode, std::ptr::null_mut()) };
+//!         // This also stops the timer.
+//!         drop(unsafe { Box::from_raw(state) });
+//!     }
+//! }
+//! ```
+
+use {
+    crate::{mode_get_private_data, view, Mode},
+    ::std::{
+        cell::Cell,
+        fmt::{self, Debug, Formatter},
+        os::raw::c_uint,
+        rc::Rc,
+        time::Duration,
+    },
+};
+
+#[cfg(doc)]
+use crate::mode_set_private_data;
+
+/// A periodic callback on a mode's private data,
+/// which is stopped when this is dropped.
+///
+/// See the [module documentation](self) for an example.
+pub struct Timer {
+    source_id: c_uint,
+    /// Whether the GLib source still exists.
+    active: Rc<Cell<bool>>,
+}
+
+struct TimerData<F> {
+    mode: *const Mode,
+    callback: F,
+    active: Rc<Cell<bool>>,
+}
+
+impl Timer {
+    /// Call `callback` every `interval` on Rofi's main thread,
+    /// with the private data of `mode` as a `T`,
+    /// and reload the view whenever it returns `true`.
+    ///
+    /// The first call happens after one interval.
+    /// The timer stops when it is dropped or when the private data of `mode` is null.
+    /// Intervals are rounded down to whole milliseconds.
+    ///
+    /// This must be called from Rofi's main thread.
+    ///
+    /// # Safety
+    ///
+    /// `mode` must stay valid until the timer is stopped,
+    /// and while it runs its private data must be null or point to a valid `T`
+    /// that is not otherwise borrowed when the main loop runs the callback.
+    pub unsafe fn start<T, F>(mode: *const Mode, interval: Duration, callback: F) -> Self
+    where
+        T: 'static,
+        F: FnMut(&mut T) -> bool + 'static,
+    {
+        let active = Rc::new(Cell::new(true));
+        let data = Box::new(TimerData {
+            mode,
+            callback,
+            active: Rc::clone(&active),
+        });
+        let interval = c_uint::try_from(interval.as_millis()).unwrap_or(c_uint::MAX);
+        let source_id = unsafe {
+            glib_sys::g_timeout_add_full(
+                glib_sys::G_PRIORITY_DEFAULT,
+                interval,
+                Some(tick::<T, F>),
+                Box::into_raw(data).cast(),
+                Some(free_data::<F>),
+            )
+        };
+        Self { source_id, active }
+    }
+
+    /// Whether the timer is still running.
+    pub fn is_active(&self) -> bool {
+        self.active.get()
+    }
+}
+
+unsafe extern "C" fn tick<T, F>(data: glib_sys::gpointer) -> glib_sys::gboolean
+where
+    F: FnMut(&mut T) -> bool,
+{
+    let data = unsafe { &mut *data.cast::<TimerData<F>>() };
+    let private_data = unsafe { mode_get_private_data(data.mode) }.cast::<T>();
+    let private_data = match unsafe { private_data.as_mut() } {
+        Some(private_data) => private_data,
+        None => return glib_sys::G_SOURCE_REMOVE,
+    };
+    if (data.callback)(private_data) {
+        unsafe { view::reload() };
+    }
+    glib_sys::G_SOURCE_CONTINUE
+}
+
+unsafe extern "C" fn free_data<F>(data: glib_sys::gpointer) {
+    let data = unsafe { Box::from_raw(data.cast::<TimerData<F>>()) };
+    data.active.set(false);
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if self.active.get() {
+            unsafe { glib_sys::g_source_remove(self.source_id) };
+        }
+    }
+}
+
+impl Debug for Timer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timer")
+            .field("source_id", &self.source_id)
+            .field("active", &self.is_active())
+            .finish()
+    }
+}
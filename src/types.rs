@@ -3,9 +3,12 @@
 use ::{
     bitflags::bitflags,
     std::{
-        ffi::c_void,
+        ffi::{c_void, CStr},
+        fmt::{self, Debug, Formatter},
+        marker::PhantomData,
         mem,
         os::raw::{c_char, c_double, c_int, c_uint},
+        ptr,
     },
 };
 
@@ -39,6 +42,16 @@ extern "C" {
     pub static ProtypeTypeName: [*const c_char; PropertyType::__Count as usize];
 }
 
+impl PropertyType {
+    /// Get the user-readable name of the type, from [`ProtypeTypeName`].
+    ///
+    /// Returns `None` for the hidden count variant, which has no name.
+    pub fn name(self) -> Option<&'static CStr> {
+        let name = unsafe { ProtypeTypeName.get(self as usize)? };
+        Some(unsafe { CStr::from_ptr(*name) })
+    }
+}
+
 bitflags! {
     /// Style of text highlight.
     #[repr(transparent)]
@@ -60,6 +73,11 @@ const _: [(); mem::size_of::<RofiHighlightStyle>()] = [(); mem::size_of::<Proper
 
 /// Style of line.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 #[repr(C)]
 pub enum RofiLineStyle {
     /// Solid line
@@ -70,6 +88,11 @@ pub enum RofiLineStyle {
 
 /// Distance unit type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 #[repr(C)]
 pub enum RofiPixelUnit {
     /// PixelWidth in pixels.
@@ -153,6 +176,7 @@ impl Default for RofiCursorType {
 
 /// Represents the color in a theme.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
 #[repr(C)]
 pub struct ThemeColor {
     /// Red channel
@@ -236,6 +260,7 @@ bitflags! {
     /// EAST            CENTER     EAST
     /// SOUTH_WEST      SOUTH      SOUTH_EAST
     /// ```
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     #[repr(transparent)]
     pub struct WindowLocation: c_uint {
         const CENTER = 0;
@@ -308,6 +333,183 @@ pub struct Property {
     pub value: PropertyValue,
 }
 
+impl Debug for Property {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Property")
+            .field("name", &self.name)
+            .field("ty", &self.ty)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A typed view of a [`Property`]'s value, as selected by its [`PropertyType`].
+///
+/// ```no_run
+/// use rofi_plugin_sys::{Property, PropertyRef};
+///
+/// # let property: &Property = unimplemented!();
+/// match unsafe { PropertyRef::new(property) } {
+///     PropertyRef::Integer(i) => println!("{i}"),
+///     PropertyRef::List(items) => println!("{} items", items.count()),
+///     other => println!("{:?} property", other.ty().name()),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum PropertyRef<'a> {
+    /// An integer, such as `4`.
+    Integer(i32),
+    /// A floating-point number, such as `0.5`.
+    Double(f64),
+    /// A string, such as `"text"`.
+    String(&'a CStr),
+    /// A character, such as `'|'`.
+    Char(c_char),
+    /// A boolean, `true` or `false`.
+    Boolean(bool),
+    /// A color, such as `#ff8800` or `rgba(255, 0, 0, 50%)`.
+    Color(ThemeColor),
+    /// An image, such as `url("bg.png")` or a gradient.
+    Image(&'a RofiImage),
+    /// One to four distances, such as `4px 8px`.
+    Padding(&'a RofiPadding),
+    /// A reference to another property, such as `@foreground`.
+    Link {
+        /// The name of the referenced property.
+        name: &'a CStr,
+        /// The referenced property, if it has been looked up.
+        resolved: Option<&'a Property>,
+        /// The value to use if the referenced property does not exist.
+        default: Option<&'a Property>,
+    },
+    /// A position, such as `south east`.
+    Position(WindowLocation),
+    /// A highlight style, such as `bold underline #ff0000`.
+    Highlight(RofiHighlightColorStyle),
+    /// A list of properties, such as `[ "a", "b" ]`.
+    List(GListIter<'a, Property>),
+    /// An orientation, `vertical` or `horizontal`.
+    Orientation(RofiOrientation),
+    /// A mouse cursor, `default`, `pointer` or `text`.
+    Cursor(RofiCursorType),
+    /// The `inherit` keyword, which takes the value from the parent widget.
+    Inherit,
+}
+
+impl<'a> PropertyRef<'a> {
+    /// Read the value of `property` according to its type.
+    ///
+    /// # Safety
+    ///
+    /// The field of `property.value` selected by `property.ty` must be initialized and valid,
+    /// as it is for properties created by Rofi:
+    /// strings must be valid C strings, links and lists must point to valid properties
+    /// and enums must hold valid variants.
+    /// These must stay valid and unmodified for `'a`.
+    pub unsafe fn new(property: &'a Property) -> Self {
+        let value = &property.value;
+        unsafe {
+            match property.ty {
+                PropertyType::Integer => Self::Integer(value.i),
+                PropertyType::Double => Self::Double(value.f),
+                PropertyType::String => Self::String(CStr::from_ptr(value.s)),
+                PropertyType::Char => Self::Char(value.c),
+                PropertyType::Boolean => Self::Boolean(value.b != 0),
+                PropertyType::Color => Self::Color(value.color),
+                PropertyType::Image => Self::Image(&value.image),
+                PropertyType::Padding => Self::Padding(&value.padding),
+                PropertyType::Link => Self::Link {
+                    name: CStr::from_ptr(value.link.name),
+                    resolved: value.link.property_ref.as_ref(),
+                    default: value.link.def_value.as_ref(),
+                },
+                PropertyType::Position => {
+                    Self::Position(WindowLocation::from_bits_retain(value.i as c_uint))
+                }
+                PropertyType::Highlight => Self::Highlight(value.highlight),
+                PropertyType::List => Self::List(GListIter::new(value.list)),
+                PropertyType::Orientation => {
+                    Self::Orientation(*ptr::addr_of!(value.i).cast::<RofiOrientation>())
+                }
+                PropertyType::Cursor => {
+                    Self::Cursor(*ptr::addr_of!(value.i).cast::<RofiCursorType>())
+                }
+                PropertyType::Inherit | PropertyType::__Count => Self::Inherit,
+            }
+        }
+    }
+
+    /// The type of the property.
+    pub fn ty(&self) -> PropertyType {
+        match self {
+            Self::Integer(_) => PropertyType::Integer,
+            Self::Double(_) => PropertyType::Double,
+            Self::String(_) => PropertyType::String,
+            Self::Char(_) => PropertyType::Char,
+            Self::Boolean(_) => PropertyType::Boolean,
+            Self::Color(_) => PropertyType::Color,
+            Self::Image(_) => PropertyType::Image,
+            Self::Padding(_) => PropertyType::Padding,
+            Self::Link { .. } => PropertyType::Link,
+            Self::Position(_) => PropertyType::Position,
+            Self::Highlight(_) => PropertyType::Highlight,
+            Self::List(_) => PropertyType::List,
+            Self::Orientation(_) => PropertyType::Orientation,
+            Self::Cursor(_) => PropertyType::Cursor,
+            Self::Inherit => PropertyType::Inherit,
+        }
+    }
+}
+
+/// An iterator over the elements of a `GList` whose data pointers point to `T`s.
+pub struct GListIter<'a, T> {
+    node: *const glib_sys::GList,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> GListIter<'a, T> {
+    /// Iterate over `list`, which may be null for an empty list.
+    ///
+    /// # Safety
+    ///
+    /// `list` must be null or a valid `GList`
+    /// whose data pointers point to valid `T`s,
+    /// and the list and its elements must stay valid and unmodified for `'a`.
+    pub unsafe fn new(list: *const glib_sys::GList) -> Self {
+        Self {
+            node: list,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for GListIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = unsafe { self.node.as_ref() }?;
+        self.node = node.next;
+        Some(unsafe { &*node.data.cast::<T>() })
+    }
+}
+
+impl<T> Clone for GListIter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for GListIter<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GListIter")
+            .field("node", &self.node)
+            .finish()
+    }
+}
+
 /// Describes the media constraint type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(C)]
@@ -7,6 +7,14 @@
 //! As such, the only functions bound here are functions with explicit conformation from the author
 //! that they are public.
 
+use ::std::{
+    iter, mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+};
+
 extern "C" {
     /// Indicate the current view needs to reload its data.
     /// This can only be done when *more* information is available.
@@ -23,6 +31,161 @@ extern "C" {
     ///
     /// See [this
     /// comment](https://github.com/davatorium/rofi/issues/2276#issuecomment-4108785896).
+    ///
+    /// **Not available with the `rofi-1-7` feature.**
+    #[cfg(not(rofi_1_7))]
     #[link_name = "rofi_view_hide"]
     pub fn hide();
 }
+
+/// A handle that requests a [`reload`] of the view from any thread.
+///
+/// Each request schedules the reload on GLib's default main context,
+/// which is the one Rofi's main loop runs,
+/// so [`reload`] is always called from Rofi's main thread.
+/// Requests made before the scheduled reload has happened are coalesced into it.
+///
+/// ```no_run
+/// use {rofi_plugin_sys::view::ReloadHandle, std::thread};
+///
+/// let reload = ReloadHandle::new();
+/// thread::spawn(move || {
+///     // ... load more data ...
+///     reload.request();
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReloadHandle {
+    pending: Arc<AtomicBool>,
+}
+
+impl ReloadHandle {
+    /// Create a new handle, with no reload pending.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request a reload of the view.
+    pub fn request(&self) {
+        if self.pending.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let pending = Arc::into_raw(Arc::clone(&self.pending));
+        unsafe {
+            glib_sys::g_idle_add_full(
+                glib_sys::G_PRIORITY_DEFAULT_IDLE,
+                Some(reload_idle),
+                pending as glib_sys::gpointer,
+                Some(drop_pending),
+            );
+        }
+    }
+
+    /// Whether a reload has been requested and has not happened yet.
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Acquire)
+    }
+}
+
+unsafe extern "C" fn reload_idle(data: glib_sys::gpointer) -> glib_sys::gboolean {
+    let pending = unsafe { &*data.cast::<AtomicBool>() };
+    // Clear the flag first, so requests made from now on schedule another reload.
+    pending.store(false, Ordering::Release);
+    unsafe { reload() };
+    glib_sys::G_SOURCE_REMOVE
+}
+
+unsafe extern "C" fn drop_pending(data: glib_sys::gpointer) {
+    drop(unsafe { Arc::from_raw(data.cast::<AtomicBool>()) });
+}
+
+/// A buffer that background producers stream entries into while the menu is open.
+///
+/// Producers on any thread [`push`](Self::push) entries into a clone of the buffer,
+/// which requests a reload of the view.
+/// The mode then [`take`](Self::take)s the new entries on Rofi's main thread,
+/// typically from its `_get_num_entries` callback, which Rofi calls when reloading.
+///
+/// ```no_run
+/// use {rofi_plugin_sys::view::EntryBuffer, std::thread};
+///
+/// struct State {
+///     entries: Vec<String>,
+///     incoming: EntryBuffer<String>,
+/// }
+///
+/// let state = State { entries: Vec::new(), incoming: EntryBuffer::new() };
+/// let incoming = state.incoming.clone();
+/// thread::spawn(move || {
+///     for i in 0..1000 {
+///         incoming.push(format!("entry {i}"));
+///     }
+/// });
+///
+/// // In `_get_num_entries`:
+/// # let mut state = state;
+/// state.entries.extend(state.incoming.take());
+/// let num_entries = state.entries.len();
+/// ```
+#[derive(Debug)]
+pub struct EntryBuffer<T> {
+    entries: Arc<Mutex<Vec<T>>>,
+    reload: ReloadHandle,
+}
+
+impl<T> EntryBuffer<T> {
+    /// Create an empty buffer.
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(Vec::new())),
+            reload: ReloadHandle::new(),
+        }
+    }
+
+    /// Add an entry to the buffer and request a reload.
+    pub fn push(&self, entry: T) {
+        self.extend(iter::once(entry));
+    }
+
+    /// Add entries to the buffer and request a reload, if there were any.
+    pub fn extend<I: IntoIterator<Item = T>>(&self, entries: I) {
+        let mut buffer = self.lock();
+        let len = buffer.len();
+        buffer.extend(entries);
+        let added = buffer.len() > len;
+        drop(buffer);
+        if added {
+            self.reload.request();
+        }
+    }
+
+    /// Take all the entries added since the last call.
+    pub fn take(&self) -> Vec<T> {
+        mem::take(&mut *self.lock())
+    }
+
+    /// The handle used to request reloads, for example to reload when production ends.
+    pub fn reload_handle(&self) -> &ReloadHandle {
+        &self.reload
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<T>> {
+        // The buffer is always in a consistent state, even if a producer panicked.
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Clone for EntryBuffer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: Arc::clone(&self.entries),
+            reload: self.reload.clone(),
+        }
+    }
+}
+
+impl<T> Default for EntryBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,283 @@
+//! Reloading a mode when the files it lists change, using GIO file monitors.
+//!
+//! **Only available with the `gio` feature.**
+//!
+//! A [`FileWatcher`] monitors files and directories,
+//! and once they have stopped changing for a while
+//! calls a loader with the mode's private data, as set with [`mode_set_private_data`],
+//! then reloads the view.
+//! Like a [`Timer`](crate::timer::Timer), it is stopped when it is dropped,
+//! so storing it in the private data stops it when the mode's `_destroy` frees the data.
+//!
+//! ```no_run
+//! use {
+//!     rofi_plugin_sys::{self as rofi, watch::FileWatcher},
+//!     std::{fs, os::raw::c_int, path::Path, time::Duration},
+//! };
+//!
+//! struct State {
+//!     bookmarks: Vec<String>,
+//!     watcher: Option<FileWatcher>,
+//! }
+//!
+//! fn load(path: &Path) -> Vec<String> {
+//!     fs::read_to_string(path)
+//!         .unwrap_or_default()
+//!         .lines()
+//!         .map(str::to_owned)
+//!         .collect()
+//! }
+//!
+//! unsafe extern "C" fn init(mode: *mut rofi::Mode) -> c_int {
+//!     let path = Path::new("/home/me/bookmarks.txt");
+//!     let state = Box::new(State { bookmarks: load(path), watcher: None });
+//!     unsafe { rofi::mode_set_private_data(mode, Box::into_raw(state).cast()) };
+//!     let watcher = unsafe {
+//!         FileWatcher::start(mode, [path], Duration::from_millis(100), move |state: &mut State| {
+//!             state.bookmarks = load(path);
+//!             true
+//!         })
+//!     };
+//!     let state = unsafe { &mut *rofi::mode_get_private_data(mode).cast::<State>() };
+//!     state.watcher = watcher.ok();
+//!     1
+//! }
+//! ```
+
+use {
+    crate::{mode_get_private_data, view, Mode},
+    ::std::{
+        cell::{Cell, RefCell},
+        error::Error,
+        ffi::{c_void, CStr, CString},
+        fmt::{self, Debug, Display, Formatter},
+        mem,
+        os::{
+            raw::{c_char, c_uint, c_ulong},
+            unix::ffi::OsStrExt,
+        },
+        path::{Path, PathBuf},
+        ptr,
+        rc::Rc,
+        time::Duration,
+    },
+};
+
+#[cfg(doc)]
+use crate::mode_set_private_data;
+
+/// Watches files and directories, reloading a mode when they change.
+///
+/// See the [module documentation](self) for an example.
+pub struct FileWatcher {
+    monitors: Vec<(*mut gio_sys::GFileMonitor, c_ulong)>,
+    /// Also owned by the signal handlers and the pending timeout source,
+    /// so that it outlives a loader that drops the watcher.
+    shared: Rc<Shared>,
+}
+
+struct Shared {
+    mode: *const Mode,
+    debounce: c_uint,
+    loader: RefCell<Box<dyn FnMut(*mut c_void) -> bool>>,
+    /// The timeout source that will run the loader, or 0.
+    pending: Cell<c_uint>,
+}
+
+/// An error returned when a path could not be watched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchError {
+    path: PathBuf,
+    message: String,
+}
+
+impl WatchError {
+    /// The path that could not be watched.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Display for WatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to watch {}: {}",
+            self.path.display(),
+            self.message
+        )
+    }
+}
+
+impl Error for WatchError {}
+
+impl FileWatcher {
+    /// Watch `paths`, and call `loader` with the private data of `mode` as a `T`
+    /// once `debounce` has passed without any of them changing,
+    /// then reload the view if it returns `true`.
+    ///
+    /// Directories are watched for changes to the files directly in them.
+    /// Paths that do not exist yet can be watched, and are reported once they are created.
+    /// The loader is not called while the private data of `mode` is null,
+    /// and may drop the watcher, for example by clearing it from the private data.
+    /// `debounce` is rounded down to whole milliseconds.
+    ///
+    /// This must be called from Rofi's main thread.
+    ///
+    /// # Safety
+    ///
+    /// `mode` must stay valid until the watcher is dropped,
+    /// and its private data must be null or point to a valid `T`
+    /// that is not otherwise borrowed when the main loop runs the loader.
+    pub unsafe fn start<T, F, I>(
+        mode: *const Mode,
+        paths: I,
+        debounce: Duration,
+        mut loader: F,
+    ) -> Result<Self, WatchError>
+    where
+        T: 'static,
+        F: FnMut(&mut T) -> bool + 'static,
+        I: IntoIterator,
+        I::Item: AsRef<Path>,
+    {
+        let mut watcher = Self {
+            monitors: Vec::new(),
+            shared: Rc::new(Shared {
+                mode,
+                debounce: c_uint::try_from(debounce.as_millis()).unwrap_or(c_uint::MAX),
+                loader: RefCell::new(Box::new(move |data| {
+                    loader(unsafe { &mut *data.cast::<T>() })
+                })),
+                pending: Cell::new(0),
+            }),
+        };
+        for path in paths {
+            // On error, dropping the watcher disconnects the monitors created so far.
+            let monitor = unsafe { monitor(path.as_ref())? };
+            let handler = unsafe {
+                gobject_sys::g_signal_connect_data(
+                    monitor.cast(),
+                    b"changed\0".as_ptr().cast::<c_char>(),
+                    Some(mem::transmute::<
+                        unsafe extern "C" fn(
+                            *mut gio_sys::GFileMonitor,
+                            *mut gio_sys::GFile,
+                            *mut gio_sys::GFile,
+                            gio_sys::GFileMonitorEvent,
+                            glib_sys::gpointer,
+                        ),
+                        unsafe extern "C" fn(),
+                    >(changed)),
+                    Rc::into_raw(Rc::clone(&watcher.shared)) as glib_sys::gpointer,
+                    Some(release_closure),
+                    0,
+                )
+            };
+            watcher.monitors.push((monitor, handler));
+        }
+        Ok(watcher)
+    }
+}
+
+unsafe fn monitor(path: &Path) -> Result<*mut gio_sys::GFileMonitor, WatchError> {
+    let error = |message: String| WatchError {
+        path: path.to_owned(),
+        message,
+    };
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| error("paths may not contain NUL bytes".to_owned()))?;
+    let file = unsafe { gio_sys::g_file_new_for_path(c_path.as_ptr()) };
+    let mut gerror = ptr::null_mut();
+    let monitor = unsafe {
+        gio_sys::g_file_monitor(
+            file,
+            gio_sys::G_FILE_MONITOR_WATCH_MOVES,
+            ptr::null_mut(),
+            &mut gerror,
+        )
+    };
+    unsafe { gobject_sys::g_object_unref(file.cast()) };
+    if monitor.is_null() {
+        let message = match unsafe { gerror.as_ref() } {
+            Some(gerror) => unsafe { CStr::from_ptr(gerror.message) }
+                .to_string_lossy()
+                .into_owned(),
+            None => "unknown error".to_owned(),
+        };
+        unsafe { glib_sys::g_clear_error(&mut gerror) };
+        return Err(error(message));
+    }
+    Ok(monitor)
+}
+
+unsafe extern "C" fn changed(
+    _monitor: *mut gio_sys::GFileMonitor,
+    _file: *mut gio_sys::GFile,
+    _other_file: *mut gio_sys::GFile,
+    event: gio_sys::GFileMonitorEvent,
+    data: glib_sys::gpointer,
+) {
+    if event == gio_sys::G_FILE_MONITOR_EVENT_ATTRIBUTE_CHANGED {
+        return;
+    }
+    let shared = unsafe { &*data.cast::<Shared>() };
+    let pending = shared.pending.replace(0);
+    if pending != 0 {
+        unsafe { glib_sys::g_source_remove(pending) };
+    }
+    // The source owns a reference, which GLib keeps alive while it runs the loader.
+    unsafe { Rc::increment_strong_count(data.cast::<Shared>()) };
+    let source = unsafe {
+        glib_sys::g_timeout_add_full(
+            glib_sys::G_PRIORITY_DEFAULT,
+            shared.debounce,
+            Some(load),
+            data,
+            Some(release),
+        )
+    };
+    shared.pending.set(source);
+}
+
+unsafe extern "C" fn load(data: glib_sys::gpointer) -> glib_sys::gboolean {
+    let shared = unsafe { &*data.cast::<Shared>() };
+    shared.pending.set(0);
+    let private_data = unsafe { mode_get_private_data(shared.mode) };
+    if !private_data.is_null() && (shared.loader.borrow_mut())(private_data) {
+        unsafe { view::reload() };
+    }
+    glib_sys::G_SOURCE_REMOVE
+}
+
+unsafe extern "C" fn release(data: glib_sys::gpointer) {
+    drop(unsafe { Rc::from_raw(data.cast::<Shared>()) });
+}
+
+unsafe extern "C" fn release_closure(data: glib_sys::gpointer, _: *mut gobject_sys::GClosure) {
+    unsafe { release(data) };
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        for &(monitor, handler) in &self.monitors {
+            unsafe {
+                gobject_sys::g_signal_handler_disconnect(monitor.cast(), handler);
+                gio_sys::g_file_monitor_cancel(monitor);
+                gobject_sys::g_object_unref(monitor.cast());
+            }
+        }
+        let pending = self.shared.pending.replace(0);
+        if pending != 0 {
+            unsafe { glib_sys::g_source_remove(pending) };
+        }
+    }
+}
+
+impl Debug for FileWatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileWatcher")
+            .field("monitors", &self.monitors.len())
+            .finish_non_exhaustive()
+    }
+}
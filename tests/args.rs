@@ -0,0 +1,75 @@
+//! Reads command-line arguments with `rofi_plugin_sys::helper::Args`,
+//! through the `find_arg*` functions of `rofi_plugin_sys::mock`.
+
+use {
+    rofi_plugin_sys::{
+        helper::{Args, FromArg},
+        mock, RofiRangePair,
+    },
+    std::ffi::CString,
+};
+
+#[test]
+fn finds_keys() {
+    mock::set_arguments(["-show", "notes", "-Verbose", "-no-config"]);
+    let args = Args::new();
+    assert_eq!(args.position("-show"), Some(1));
+    assert_eq!(args.position("-verbose"), Some(3));
+    assert!(args.has("-no-config"));
+    assert!(!args.has("-notes"));
+    assert_eq!(args.position("-missing"), None);
+}
+
+#[test]
+fn gets_values() {
+    mock::set_arguments([
+        "-depth", "42", "-offset", "-7px", "-sep", "\\x1f", "-name", "notes", "-flag",
+    ]);
+    let args = Args::new();
+    assert_eq!(args.get::<u32>("-depth"), Some(42));
+    assert_eq!(args.get::<i32>("-offset"), Some(-7));
+    assert_eq!(args.get::<char>("-sep"), Some('\x1f'));
+    assert_eq!(args.get::<String>("-name").as_deref(), Some("notes"));
+    assert_eq!(args.get::<String>("-missing"), None);
+    // The last argument has no value.
+    assert_eq!(args.get::<String>("-flag"), None);
+}
+
+#[test]
+fn gets_all_values() {
+    mock::set_arguments(["-path", "a", "-other", "b", "-path", "c"]);
+    let args = Args::new();
+    assert_eq!(args.get_all("-path"), ["a", "c"]);
+    assert_eq!(args.get_all("-missing"), Vec::<String>::new());
+    // Like Rofi, `find_arg_str` takes the value of the first occurrence.
+    assert_eq!(args.get::<String>("-path").as_deref(), Some("a"));
+}
+
+#[test]
+fn gets_ranges() {
+    mock::set_arguments(["-selected", "1,3-5,7-"]);
+    let ranges = Args::new().get_ranges("-selected").unwrap();
+    let ranges: Vec<_> = ranges
+        .iter()
+        .map(|&RofiRangePair { start, stop }| (start, stop))
+        .collect();
+    assert_eq!(ranges, [(1, 1), (3, 5), (7, -1)]);
+}
+
+#[test]
+fn from_arg_uses_raw_key() {
+    mock::set_arguments(["-count", "12"]);
+    let key = CString::new("-count").unwrap();
+    assert_eq!(u32::find_arg(&key), Some(12));
+    let key = CString::new("-missing").unwrap();
+    assert_eq!(u32::find_arg(&key), None);
+}
+
+#[test]
+fn without_arguments() {
+    mock::reset();
+    let args = Args::new();
+    assert!(!args.has("-show"));
+    assert_eq!(args.get::<u32>("-depth"), None);
+    assert!(args.get_all("-path").is_empty());
+}
@@ -0,0 +1,100 @@
+//! Builders for theme and configuration sections,
+//! laid out like Rofi's theme parser lays them out.
+//!
+//! Everything built here is leaked, as Rofi keeps its parsed theme until it exits.
+
+// Not every test uses every builder.
+#![allow(dead_code)]
+
+use {
+    rofi_plugin_sys::{
+        glib_sys, Property, PropertyType, PropertyValue, RofiDistance, RofiDistanceModifier,
+        RofiDistanceUnit, RofiLineStyle, RofiPixelUnit, ThemeMedia, ThemeWidget,
+    },
+    std::{ffi::CString, os::raw::c_char, ptr},
+};
+
+pub fn leak_str(s: &str) -> *mut c_char {
+    CString::new(s).unwrap().into_raw()
+}
+
+pub fn property(name: &str, ty: PropertyType, value: PropertyValue) -> *mut Property {
+    Box::into_raw(Box::new(Property {
+        name: leak_str(name),
+        ty,
+        value,
+    }))
+}
+
+/// A single value with a unit, such as `4px`.
+pub fn unit(distance: f64, unit_type: RofiPixelUnit) -> RofiDistanceUnit {
+    RofiDistanceUnit {
+        distance,
+        unit_type,
+        mod_type: RofiDistanceModifier::None,
+        left: ptr::null_mut(),
+        right: ptr::null_mut(),
+    }
+}
+
+/// An operation in a `calc()` expression, with `right` omitted for unary operations.
+pub fn op(
+    mod_type: RofiDistanceModifier,
+    left: RofiDistanceUnit,
+    right: Option<RofiDistanceUnit>,
+) -> RofiDistanceUnit {
+    RofiDistanceUnit {
+        mod_type,
+        left: Box::into_raw(Box::new(left)),
+        right: right.map_or(ptr::null_mut(), |right| Box::into_raw(Box::new(right))),
+        ..unit(0.0, RofiPixelUnit::Px)
+    }
+}
+
+/// A solid distance.
+pub fn distance(base: RofiDistanceUnit) -> RofiDistance {
+    RofiDistance {
+        base,
+        style: RofiLineStyle::Solid,
+    }
+}
+
+/// A widget, appended to the children of `parent` if it is not null.
+pub fn widget(
+    name: &str,
+    parent: *mut ThemeWidget,
+    media: Option<ThemeMedia>,
+    properties: &[*mut Property],
+) -> *mut ThemeWidget {
+    let table = if properties.is_empty() {
+        ptr::null_mut()
+    } else {
+        unsafe {
+            glib_sys::g_hash_table_new(Some(glib_sys::g_str_hash), Some(glib_sys::g_str_equal))
+        }
+    };
+    for &property in properties {
+        unsafe { glib_sys::g_hash_table_replace(table, (*property).name as _, property.cast()) };
+    }
+    let widget = Box::into_raw(Box::new(ThemeWidget {
+        set: 1,
+        name: leak_str(name),
+        num_widgets: 0,
+        widgets: ptr::null_mut(),
+        media: media.map_or(ptr::null_mut(), |media| Box::into_raw(Box::new(media))),
+        properties: table,
+        parent,
+    }));
+    if let Some(parent) = unsafe { parent.as_mut() } {
+        let mut children = if parent.widgets.is_null() {
+            Vec::new()
+        } else {
+            let len = parent.num_widgets as usize;
+            unsafe { Vec::from_raw_parts(parent.widgets, len, len) }
+        };
+        children.push(widget);
+        parent.num_widgets = children.len() as _;
+        parent.widgets = Box::leak(children.into_boxed_slice()).as_mut_ptr();
+    }
+    widget
+}
@@ -0,0 +1,216 @@
+//! Checks `rofi_plugin_sys::de` against configuration sections built by hand,
+//! laid out like Rofi's theme parser lays them out.
+
+mod common;
+
+use {
+    common::{distance, leak_str, property, unit, widget},
+    rofi_plugin_sys::{
+        de::{self, Distance, Padding},
+        glib_sys, PropertyType, PropertyValue, RofiLineStyle, RofiPadding, RofiPixelUnit,
+        ThemeColor,
+    },
+    serde::Deserialize,
+    std::{os::raw::c_char, ptr},
+};
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+struct Config {
+    notes_dir: String,
+    max_results: i32,
+    ratio: f64,
+    show_preview: bool,
+    separator: char,
+    extensions: Vec<String>,
+    highlight: ThemeColor,
+    spacing: Distance,
+    margin: Padding,
+    #[serde(default)]
+    missing: Option<i32>,
+    nested: Nested,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Nested {
+    depth: u8,
+}
+
+#[test]
+fn deserializes_section() {
+    let list = [
+        property(
+            "",
+            PropertyType::String,
+            PropertyValue { s: leak_str("md") },
+        ),
+        property(
+            "",
+            PropertyType::String,
+            PropertyValue { s: leak_str("txt") },
+        ),
+    ]
+    .iter()
+    .fold(ptr::null_mut(), |list, &item| unsafe {
+        glib_sys::g_list_append(list, item.cast())
+    });
+    let color = ThemeColor {
+        red: 1.0,
+        green: 0.5,
+        blue: 0.0,
+        alpha: 1.0,
+    };
+    let spacing = distance(unit(4.0, RofiPixelUnit::Px));
+    let section = widget(
+        "my-plugin",
+        ptr::null_mut(),
+        None,
+        &[
+            property(
+                "notes-dir",
+                PropertyType::String,
+                PropertyValue {
+                    s: leak_str("~/notes"),
+                },
+            ),
+            property(
+                "max-results",
+                PropertyType::Integer,
+                PropertyValue { i: 20 },
+            ),
+            property("ratio", PropertyType::Double, PropertyValue { f: 0.25 }),
+            property(
+                "show-preview",
+                PropertyType::Boolean,
+                PropertyValue { b: 1 },
+            ),
+            property(
+                "separator",
+                PropertyType::Char,
+                PropertyValue { c: b'|' as c_char },
+            ),
+            property("extensions", PropertyType::List, PropertyValue { list }),
+            property("highlight", PropertyType::Color, PropertyValue { color }),
+            property(
+                "spacing",
+                PropertyType::Padding,
+                PropertyValue {
+                    padding: RofiPadding {
+                        top: spacing,
+                        right: spacing,
+                        bottom: spacing,
+                        left: spacing,
+                    },
+                },
+            ),
+            property(
+                "margin",
+                PropertyType::Padding,
+                PropertyValue {
+                    padding: RofiPadding {
+                        top: distance(unit(1.0, RofiPixelUnit::Em)),
+                        right: distance(unit(10.0, RofiPixelUnit::Percent)),
+                        bottom: distance(unit(1.0, RofiPixelUnit::Em)),
+                        left: distance(unit(10.0, RofiPixelUnit::Percent)),
+                    },
+                },
+            ),
+            property("ignored", PropertyType::Inherit, PropertyValue { i: 0 }),
+        ],
+    );
+    widget(
+        "nested",
+        section,
+        None,
+        &[property(
+            "depth",
+            PropertyType::Integer,
+            PropertyValue { i: 3 },
+        )],
+    );
+
+    let config: Config = unsafe { de::from_widget(section) }.unwrap();
+    let em = Distance {
+        value: 1.0,
+        unit: RofiPixelUnit::Em,
+        style: RofiLineStyle::Solid,
+    };
+    let percent = Distance {
+        value: 10.0,
+        unit: RofiPixelUnit::Percent,
+        style: RofiLineStyle::Solid,
+    };
+    assert_eq!(
+        config,
+        Config {
+            notes_dir: "~/notes".to_owned(),
+            max_results: 20,
+            ratio: 0.25,
+            show_preview: true,
+            separator: '|',
+            extensions: vec!["md".to_owned(), "txt".to_owned()],
+            highlight: color,
+            spacing: Distance {
+                value: 4.0,
+                unit: RofiPixelUnit::Px,
+                style: RofiLineStyle::Solid,
+            },
+            margin: Padding {
+                top: em,
+                right: percent,
+                bottom: em,
+                left: percent,
+            },
+            missing: None,
+            nested: Nested { depth: 3 },
+        }
+    );
+}
+
+#[test]
+fn reports_errors() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Single {
+        margin: Distance,
+    }
+
+    let margin = property(
+        "margin",
+        PropertyType::Padding,
+        PropertyValue {
+            padding: RofiPadding {
+                top: distance(unit(1.0, RofiPixelUnit::Px)),
+                right: distance(unit(2.0, RofiPixelUnit::Px)),
+                bottom: distance(unit(1.0, RofiPixelUnit::Px)),
+                left: distance(unit(2.0, RofiPixelUnit::Px)),
+            },
+        },
+    );
+    let section = widget("my-plugin", ptr::null_mut(), None, &[margin]);
+    let error = unsafe { de::from_widget::<Single>(section) }.unwrap_err();
+    assert!(error.to_string().contains("different sides"), "{error}");
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Typed {
+        count: i32,
+    }
+
+    let count = property(
+        "count",
+        PropertyType::String,
+        PropertyValue {
+            s: leak_str("many"),
+        },
+    );
+    let section = widget("my-plugin", ptr::null_mut(), None, &[count]);
+    assert!(unsafe { de::from_widget::<Typed>(section) }.is_err());
+
+    let section = widget("my-plugin", ptr::null_mut(), None, &[]);
+    let error = unsafe { de::from_widget::<Typed>(section) }.unwrap_err();
+    assert!(
+        error.to_string().contains("missing field `count`"),
+        "{error}"
+    );
+}
@@ -0,0 +1,44 @@
+//! Runs commands with `rofi_plugin_sys::helper::execute_generator_str`
+//! and its asynchronous variant, through the run command of `rofi_plugin_sys::mock`.
+
+use {
+    rofi_plugin_sys::{glib_sys, helper, mock, view::EntryBuffer},
+    std::{
+        io::{BufRead, BufReader},
+        ptr,
+        time::{Duration, Instant},
+    },
+};
+
+// A single test, so that the reloads are always dispatched on the thread that checks them.
+#[test]
+fn executes_generators() {
+    reads_output();
+    streams_output();
+}
+
+fn reads_output() {
+    let output = helper::execute_generator_str("printf 'a\\nb c\\n'").unwrap();
+    let lines: Vec<_> = BufReader::new(output)
+        .lines()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(lines, ["a", "b c"]);
+}
+
+fn streams_output() {
+    mock::take_calls();
+    let entries = EntryBuffer::new();
+    helper::execute_generator_lines("printf 'one\\ntwo\\nthree'", &entries).unwrap();
+
+    let mut lines = Vec::new();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while lines.len() < 3 {
+        assert!(Instant::now() < deadline, "timed out with {:?}", lines);
+        unsafe { glib_sys::g_main_context_iteration(ptr::null_mut(), 0) };
+        lines.extend(entries.take());
+    }
+    assert_eq!(lines, ["one", "two", "three"]);
+    while unsafe { glib_sys::g_main_context_iteration(ptr::null_mut(), 0) } != 0 {}
+    assert!(mock::take_calls().contains(&mock::Call::Reload));
+}
@@ -0,0 +1,119 @@
+//! Highlights matches with `rofi_plugin_sys::helper::append_highlight_attributes`,
+//! using the Pango helpers provided by `rofi_plugin_sys::mock`.
+
+use {
+    rofi_plugin_sys::{
+        glib_sys,
+        helper::{self, Tokens},
+        pango_sys, RofiHighlightColorStyle, RofiHighlightStyle, ThemeColor,
+    },
+    std::{ffi::CString, ptr},
+};
+
+/// The type, start and end of an attribute, which sort by type first.
+type Span = (pango_sys::PangoAttrType, u32, u32);
+
+fn highlight(tokens: &Tokens, style: RofiHighlightStyle, alpha: f64, input: &str) -> Vec<Span> {
+    let style = RofiHighlightColorStyle {
+        style,
+        color: ThemeColor {
+            red: 1.0,
+            green: 0.0,
+            blue: 0.5,
+            alpha,
+        },
+    };
+    let input = CString::new(input).unwrap();
+    let mut list = ptr::null_mut();
+    unsafe { helper::append_highlight_attributes(&mut list, tokens.as_ptr(), style, &input) };
+
+    let mut spans = Vec::new();
+    let mut node = list;
+    while !node.is_null() {
+        let attr = unsafe { (*node).data.cast::<pango_sys::PangoAttribute>() };
+        let attr_type = unsafe { (*(*attr).klass).type_ };
+        match attr_type {
+            pango_sys::PANGO_ATTR_WEIGHT => {
+                let value = unsafe { (*attr.cast::<pango_sys::PangoAttrInt>()).value };
+                assert_eq!(value, pango_sys::PANGO_WEIGHT_BOLD);
+            }
+            pango_sys::PANGO_ATTR_FOREGROUND => {
+                let color = unsafe { (*attr.cast::<pango_sys::PangoAttrColor>()).color };
+                assert_eq!((color.red, color.green, color.blue), (65535, 0, 32767));
+            }
+            pango_sys::PANGO_ATTR_FOREGROUND_ALPHA => {
+                let value = unsafe { (*attr.cast::<pango_sys::PangoAttrInt>()).value };
+                assert_eq!(value, 32767);
+            }
+            _ => {}
+        }
+        spans.push(unsafe { (attr_type, (*attr).start_index, (*attr).end_index) });
+        node = unsafe { (*node).next };
+    }
+    unsafe {
+        glib_sys::g_list_free_full(list, Some(destroy_attribute));
+    }
+    spans.sort_unstable();
+    spans
+}
+
+unsafe extern "C" fn destroy_attribute(attr: glib_sys::gpointer) {
+    unsafe { pango_sys::pango_attribute_destroy(attr.cast()) };
+}
+
+#[test]
+fn highlights_matches() {
+    let tokens = Tokens::new("foo", false);
+    assert_eq!(
+        highlight(&tokens, RofiHighlightStyle::BOLD, 1.0, "a foo"),
+        [(pango_sys::PANGO_ATTR_WEIGHT, 2, 5)],
+    );
+    assert_eq!(
+        highlight(
+            &tokens,
+            RofiHighlightStyle::UNDERLINE | RofiHighlightStyle::COLOR,
+            0.5,
+            "Foo foo",
+        ),
+        [
+            (pango_sys::PANGO_ATTR_FOREGROUND, 0, 3),
+            (pango_sys::PANGO_ATTR_FOREGROUND, 4, 7),
+            (pango_sys::PANGO_ATTR_UNDERLINE, 0, 3),
+            (pango_sys::PANGO_ATTR_UNDERLINE, 4, 7),
+            (pango_sys::PANGO_ATTR_FOREGROUND_ALPHA, 0, 3),
+            (pango_sys::PANGO_ATTR_FOREGROUND_ALPHA, 4, 7),
+        ],
+    );
+    assert_eq!(highlight(&tokens, RofiHighlightStyle::BOLD, 1.0, "bar"), [],);
+}
+
+#[test]
+fn skips_inverted_tokens() {
+    let tokens = Tokens::new("é -foo", false);
+    assert_eq!(
+        highlight(&tokens, RofiHighlightStyle::ITALIC, 1.0, "café foo"),
+        [(pango_sys::PANGO_ATTR_STYLE, 3, 5)],
+    );
+}
+
+#[test]
+fn ignores_null() {
+    let style = RofiHighlightColorStyle {
+        style: RofiHighlightStyle::BOLD,
+        color: ThemeColor {
+            red: 0.0,
+            green: 0.0,
+            blue: 0.0,
+            alpha: 1.0,
+        },
+    };
+    let input = CString::new("foo").unwrap();
+    let mut list = ptr::null_mut();
+    unsafe { helper::append_highlight_attributes(&mut list, ptr::null_mut(), style, &input) };
+    assert!(list.is_null());
+
+    let tokens = Tokens::new("foo", false);
+    unsafe {
+        helper::append_highlight_attributes(ptr::null_mut(), tokens.as_ptr(), style, &input);
+    }
+}
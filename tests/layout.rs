@@ -0,0 +1,430 @@
+//! Checks the size, alignment and field offsets of every `#[repr(C)]` type in the crate
+//! against the vendored Rofi headers in `headers/` for the targeted Rofi version,
+//! by compiling and running a C program that prints the layouts as C sees them.
+//! Since the Rofi 2.0 and `next` bindings share every item but those marked `cfg(rofi_next)`,
+//! a build targeting either one is checked against both sets of headers.
+//!
+//! Needs a C compiler (`$CC` or `cc`)
+//! and the GLib, GModule and Cairo development headers, found through `pkg-config`.
+//! If they are not available the test fails,
+//! unless `ROFI_PLUGIN_SYS_SKIP_LAYOUT` is set to skip it explicitly.
+
+use {
+    rofi_plugin_sys::{helper::RofiHelperExecuteContext, *},
+    std::{
+        env,
+        fmt::Write as _,
+        fs,
+        mem::{self, MaybeUninit},
+        path::{Path, PathBuf},
+        process::Command,
+    },
+};
+
+/// A value as computed in Rust, alongside the C expression that should evaluate to it.
+struct Check {
+    c: String,
+    rust: i64,
+}
+
+/// Generate checks for the size, alignment and field offsets of types.
+///
+/// Each type is given as `RustType = "C type" { rust_field = "c_field", ... }`,
+/// and both types and fields may have `#[cfg]` attributes.
+macro_rules! layouts {
+    ($checks:ident: $(
+        $(#[$attr:meta])*
+        $rust:ty = $c:literal {
+            $($(#[$field_attr:meta])* $field:ident = $c_field:literal),* $(,)?
+        }
+    )*) => {$(
+        $(#[$attr])*
+        {
+            $checks.push(Check { c: format!("sizeof({})", $c), rust: mem::size_of::<$rust>() as i64 });
+            $checks.push(Check { c: format!("_Alignof({})", $c), rust: mem::align_of::<$rust>() as i64 });
+            $(
+                $(#[$field_attr])*
+                $checks.push(Check {
+                    c: format!("offsetof({}, {})", $c, $c_field),
+                    rust: offset_of!($rust, $field) as i64,
+                });
+            )*
+        }
+    )*};
+}
+
+/// The offset of a field in bytes, like `mem::offset_of!`, which needs a newer Rust.
+macro_rules! offset_of {
+    ($ty:ty, $field:ident) => {{
+        let value = MaybeUninit::<$ty>::uninit();
+        let base = value.as_ptr();
+        // SAFETY: `addr_of!` does not read the field or create a reference to it.
+        let field = unsafe { std::ptr::addr_of!((*base).$field) };
+        field as usize - base as usize
+    }};
+}
+
+/// Generate checks for the values of constants.
+///
+/// Each constant is given as `"C constant" => rust_expression`.
+macro_rules! values {
+    ($checks:ident: $($(#[$attr:meta])* $c:literal => $rust:expr),* $(,)?) => {$(
+        $(#[$attr])*
+        $checks.push(Check { c: $c.to_owned(), rust: $rust as i64 });
+    )*};
+}
+
+fn checks() -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    layouts! { checks:
+        // rofi-types.h
+        PropertyType = "PropertyType" {}
+        RofiHighlightStyle = "RofiHighlightStyle" {}
+        RofiLineStyle = "RofiLineStyle" {}
+        RofiPixelUnit = "RofiPixelUnit" {}
+        RofiDistanceModifier = "RofiDistanceModifier" {}
+        RofiDistanceUnit = "RofiDistanceUnit" {
+            distance = "distance",
+            unit_type = "type",
+            mod_type = "modtype",
+            left = "left",
+            right = "right",
+        }
+        RofiDistance = "RofiDistance" { base = "base", style = "style" }
+        RofiOrientation = "RofiOrientation" {}
+        RofiCursorType = "RofiCursorType" {}
+        ThemeColor = "ThemeColor" { red = "red", green = "green", blue = "blue", alpha = "alpha" }
+        RofiImageType = "RofiImageType" {}
+        RofiDirection = "RofiDirection" {}
+        RofiScaleType = "RofiScaleType" {}
+        RofiImage = "RofiImage" {
+            image_type = "type",
+            url = "url",
+            scaling = "scaling",
+            wsize = "wsize",
+            hsize = "hsize",
+            dir = "dir",
+            angle = "angle",
+            colors = "colors",
+            surface_id = "surface_id",
+        }
+        RofiPadding = "RofiPadding" { top = "top", right = "right", bottom = "bottom", left = "left" }
+        RofiHighlightColorStyle = "RofiHighlightColorStyle" { style = "style", color = "color" }
+        WindowLocation = "WindowLocation" {}
+        // The C version of this type is an anonymous struct inside `PropertyValue`.
+        PropertyLink = "__typeof__(((PropertyValue *)0)->link)" {
+            name = "name",
+            property_ref = "ref",
+            def_value = "def_value",
+        }
+        PropertyValue = "PropertyValue" {
+            i = "i",
+            f = "f",
+            s = "s",
+            c = "c",
+            b = "b",
+            color = "color",
+            padding = "padding",
+            link = "link",
+            highlight = "highlight",
+            image = "image",
+            list = "list",
+        }
+        Property = "Property" { name = "name", ty = "type", value = "value" }
+        ThemeMediaType = "ThemeMediaType" {}
+        ThemeMedia = "ThemeMedia" { r#type = "type", value = "value", boolv = "boolv" }
+        ThemeWidget = "ThemeWidget" {
+            set = "set",
+            name = "name",
+            num_widgets = "num_widgets",
+            widgets = "widgets",
+            media = "media",
+            properties = "properties",
+            parent = "parent",
+        }
+        RofiRangePair = "rofi_range_pair" { start = "start", stop = "stop" }
+        RofiIntMatcher = "rofi_int_matcher" { regex = "regex", invert = "invert" }
+        ThreadState = "thread_state" { callback = "callback", free = "free", priority = "priority" }
+
+        // mode-private.h
+        EntryState = "TextBoxFontType" {}
+        #[cfg(not(rofi_1_7))]
+        ModeType = "ModeType" {}
+        Mode = "Mode" {
+            abi_version = "abi_version",
+            name = "name",
+            cfg_name_key = "cfg_name_key",
+            display_name = "display_name",
+            _init = "_init",
+            _destroy = "_destroy",
+            _get_num_entries = "_get_num_entries",
+            _result = "_result",
+            _token_match = "_token_match",
+            _get_display_value = "_get_display_value",
+            _get_icon = "_get_icon",
+            _get_completion = "_get_completion",
+            _preprocess_input = "_preprocess_input",
+            _get_message = "_get_message",
+            private_data = "private_data",
+            free = "free",
+            #[cfg(not(rofi_1_7))]
+            _create = "_create",
+            #[cfg(not(rofi_1_7))]
+            _completer_result = "_completer_result",
+            ed = "ed",
+            module = "module",
+            fallback_icon_fetch_uid = "fallback_icon_fetch_uid",
+            fallback_icon_not_found = "fallback_icon_not_found",
+            #[cfg(not(rofi_1_7))]
+            r#type = "type",
+        }
+
+        // settings.h
+        settings::MatchingMethod = "MatchingMethod" {}
+        settings::SortingMethod = "SortingMethod" {}
+        settings::Settings = "Settings" {
+            modes = "modes",
+            menu_font = "menu_font",
+            show_icons = "show_icons",
+            terminal_emulator = "terminal_emulator",
+            ssh_client = "ssh_client",
+            ssh_command = "ssh_command",
+            run_command = "run_command",
+            run_shell_command = "run_shell_command",
+            run_list_command = "run_list_command",
+            window_command = "window_command",
+            window_match_fields = "window_match_fields",
+            icon_theme = "icon_theme",
+            location = "location",
+            y_offset = "y_offset",
+            x_offset = "x_offset",
+            fixed_num_lines = "fixed_num_lines",
+            disable_history = "disable_history",
+            ignored_prefixes = "ignored_prefixes",
+            sort = "sort",
+            sorting_method_enum = "sorting_method_enum",
+            sorting_method = "sorting_method",
+            drun_match_fields = "drun_match_fields",
+            drun_categories = "drun_categories",
+            drun_show_actions = "drun_show_actions",
+            drun_display_format = "drun_display_format",
+            drun_url_launcher = "drun_url_launcher",
+            case_sensitive = "case_sensitive",
+            #[cfg(not(rofi_1_7))]
+            case_smart = "case_smart",
+            cycle = "cycle",
+            element_height = "element_height",
+            sidebar_mode = "sidebar_mode",
+            hover_select = "hover_select",
+            auto_select = "auto_select",
+            parse_hosts = "parse_hosts",
+            parse_known_hosts = "parse_known_hosts",
+            combi_modes = "combi_modes",
+            matching = "matching",
+            matching_method = "matching_method",
+            tokenize = "tokenize",
+            monitor = "monitor",
+            filter = "filter",
+            dpi = "dpi",
+            threads = "threads",
+            scroll_method = "scroll_method",
+            window_format = "window_format",
+            click_to_exit = "click_to_exit",
+            theme = "theme",
+            plugin_path = "plugin_path",
+            max_history_size = "max_history_size",
+            combi_hide_mode_prefix = "combi_hide_mode_prefix",
+            combi_display_format = "combi_display_format",
+            matching_negate_char = "matching_negate_char",
+            cache_dir = "cache_dir",
+            window_thumbnail = "window_thumbnail",
+            drun_use_desktop_cache = "drun_use_desktop_cache",
+            drun_reload_desktop_cache = "drun_reload_desktop_cache",
+            benchmark_ui = "benchmark_ui",
+            normalize_match = "normalize_match",
+            steal_focus = "steal_focus",
+            application_fallback_icon = "application_fallback_icon",
+            refilter_timeout_limit = "refilter_timeout_limit",
+            xserver_i300_workaround = "xserver_i300_workaround",
+            #[cfg(not(rofi_1_7))]
+            completer_mode = "completer_mode",
+        }
+
+        // xrmoptions.h
+        options::XrmOptionType = "XrmOptionType" {}
+
+        // helper.h
+        RofiHelperExecuteContext = "RofiHelperExecuteContext" {
+            name = "name",
+            binary = "binary",
+            description = "description",
+            icon = "icon",
+            app_id = "app_id",
+            wmclass = "wmclass",
+            command = "command",
+        }
+    }
+
+    values! { checks:
+        "ABI_VERSION" => ABI_VERSION,
+        "ROFI_HL_CAPITALIZE" => RofiHighlightStyle::CAPITALIZE.bits(),
+        "ROFI_PU_CH" => RofiPixelUnit::Ch,
+        "ROFI_DISTANCE_MODIFIER_CEIL" => RofiDistanceModifier::Ceil,
+        "ROFI_CURSOR_TEXT" => RofiCursorType::Text,
+        "ROFI_DIRECTION_ANGLE" => RofiDirection::Angle,
+        "ROFI_SCALE_WIDTH" => RofiScaleType::Width,
+        "WL_SOUTH_WEST" => WindowLocation::SOUTH_WEST.bits(),
+        "THEME_MEDIA_TYPE_INVALID" => ThemeMediaType::Invalid,
+        #[cfg(not(rofi_1_7))]
+        "MODE_TYPE_DMENU" => ModeType::DMENU.bits(),
+        "URGENT" => EntryState::URGENT.bits(),
+        "ACTIVE" => EntryState::ACTIVE.bits(),
+        "SELECTED" => EntryState::SELECTED.bits(),
+        "MARKUP" => EntryState::MARKUP.bits(),
+        "ALT" => EntryState::ALT.bits(),
+        "HIGHLIGHT" => EntryState::HIGHLIGHT.bits(),
+        "FMOD_MASK" => EntryState::FMOD_MASK.bits(),
+        "MM_PREFIX" => settings::MatchingMethod::Prefix,
+        "SORT_FZF" => settings::SortingMethod::Fzf,
+        "xrm_Char" => options::XrmOptionType::Char,
+        "MODE_EXIT" => EXIT,
+        "RESET_DIALOG" => RESET_DIALOG,
+        "MENU_OK" => menu::OK,
+        "MENU_CANCEL" => menu::CANCEL,
+        "MENU_NEXT" => menu::NEXT,
+        "MENU_CUSTOM_INPUT" => menu::CUSTOM_INPUT,
+        "MENU_ENTRY_DELETE" => menu::ENTRY_DELETE,
+        "MENU_QUICK_SWITCH" => menu::QUICK_SWITCH,
+        "MENU_CUSTOM_COMMAND" => menu::CUSTOM_COMMAND,
+        "MENU_PREVIOUS" => menu::PREVIOUS,
+        "MENU_COMPLETE" => menu::COMPLETE,
+        "MENU_CUSTOM_ACTION" => menu::CUSTOM_ACTION,
+        "MENU_LOWER_MASK" => menu::LOWER_MASK,
+    }
+
+    checks
+}
+
+/// The directories of the vendored headers that the current bindings must match.
+fn header_dirs() -> Vec<PathBuf> {
+    let variants: &[&str] = if cfg!(rofi_1_7) {
+        &["1.7"]
+    } else {
+        &["2.0", "next"]
+    };
+    variants
+        .iter()
+        .map(|variant| {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("headers")
+                .join(variant)
+        })
+        .collect()
+}
+
+fn pkg_config_cflags() -> Option<Vec<String>> {
+    let output = Command::new("pkg-config")
+        .args(["--cflags", "glib-2.0", "gmodule-2.0", "cairo"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let cflags = String::from_utf8(output.stdout).ok()?;
+    Some(cflags.split_whitespace().map(str::to_owned).collect())
+}
+
+#[test]
+fn layout_matches_headers() {
+    if env::var_os("ROFI_PLUGIN_SYS_SKIP_LAYOUT").is_some() {
+        eprintln!("skipping layout test: `ROFI_PLUGIN_SYS_SKIP_LAYOUT` is set");
+        return;
+    }
+    let cflags = pkg_config_cflags().expect(
+        "GLib, GModule or Cairo headers not found through `pkg-config` \
+         (set `ROFI_PLUGIN_SYS_SKIP_LAYOUT` to skip this test)",
+    );
+
+    let checks = checks();
+
+    let mut source = String::from(
+        "#include <stddef.h>\n\
+         #include <stdio.h>\n\
+         #include <mode.h>\n\
+         #include <mode-private.h>\n\
+         #include <helper.h>\n\
+         #include <textbox.h>\n\
+         #include <settings.h>\n\
+         #include <xrmoptions.h>\n\
+         int main(void) {\n",
+    );
+    for check in &checks {
+        writeln!(source, "    printf(\"%lld\\n\", (long long)({}));", check.c).unwrap();
+    }
+    source.push_str("    return 0;\n}\n");
+
+    let mismatches: Vec<String> = header_dirs()
+        .iter()
+        .flat_map(|header_dir| {
+            let output = run_probe(&source, header_dir, &cflags);
+            assert_eq!(output.len(), checks.len());
+            checks
+                .iter()
+                .zip(output)
+                .filter(|(check, c)| *c != check.rust)
+                .map(|(check, c)| {
+                    format!(
+                        "{}: {}: C says {c}, Rust says {}",
+                        header_dir.display(),
+                        check.c,
+                        check.rust,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert!(
+        mismatches.is_empty(),
+        "layout mismatches:\n{}",
+        mismatches.join("\n"),
+    );
+}
+
+/// Compile `source` against the headers in `header_dir`, run it
+/// and return the numbers it prints.
+fn run_probe(source: &str, header_dir: &Path, cflags: &[String]) -> Vec<i64> {
+    let variant = header_dir.file_name().unwrap();
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("layout")
+        .join(variant);
+    fs::create_dir_all(&dir).unwrap();
+    let source_path = dir.join("layout.c");
+    let exe_path = dir.join("layout");
+    fs::write(&source_path, source).unwrap();
+
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_owned());
+    let status = Command::new(&cc)
+        .arg("-std=gnu11")
+        .arg("-I")
+        .arg(header_dir)
+        .args(cflags)
+        .arg(&source_path)
+        .arg("-o")
+        .arg(&exe_path)
+        .status()
+        .unwrap_or_else(|e| panic!("failed to run `{cc}`: {e}"));
+    assert!(
+        status.success(),
+        "failed to compile {}",
+        source_path.display()
+    );
+
+    let output = Command::new(&exe_path).output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect()
+}
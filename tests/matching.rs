@@ -0,0 +1,206 @@
+//! Checks the Rust reimplementation of Rofi's matching in `rofi_plugin_sys::matching`.
+//!
+//! The differential test compares it against Rofi itself,
+//! by filtering a list of entries with `rofi -dmenu -dump`.
+//! It needs a `rofi` binary (`$ROFI` or `rofi`) that can connect to a display,
+//! so it is ignored by default; run it with `cargo test --test matching -- --ignored`.
+
+use {
+    rofi_plugin_sys::matching::{pattern, Matchers, MatchingConfig, MatchingMethod},
+    std::{
+        env,
+        io::Write as _,
+        process::{Command, Stdio},
+    },
+};
+
+const METHODS: [MatchingMethod; 5] = [
+    MatchingMethod::Normal,
+    MatchingMethod::Regex,
+    MatchingMethod::Glob,
+    MatchingMethod::Fuzzy,
+    MatchingMethod::Prefix,
+];
+
+const ENTRIES: &[&str] = &[
+    "foo",
+    "Foo Bar",
+    "foobar",
+    "bar foo",
+    "barfoo",
+    "f.o",
+    "fxo",
+    "a*b",
+    "a+b (c)",
+    "Über",
+    "über-alles",
+    "[brackets]",
+    "back\\slash",
+    "tab\tseparated",
+    "",
+];
+
+const QUERIES: &[&str] = &[
+    "", "foo", "FOO", "Foo", "fo bar", "-bar", "foo -bar", "-", "f.o", "f?o", "f*o", "fb", "^foo",
+    "o$", "a*b", "a+b", "(c", "[", "über", "ÜBER", "\\", "b",
+];
+
+fn filter(query: &str, config: &MatchingConfig) -> Vec<&'static str> {
+    let matchers = Matchers::new(query, config);
+    ENTRIES
+        .iter()
+        .copied()
+        .filter(|entry| matchers.matches(entry))
+        .collect()
+}
+
+fn config(method: MatchingMethod) -> MatchingConfig {
+    MatchingConfig {
+        method,
+        ..MatchingConfig::default()
+    }
+}
+
+/// The regular expressions built by `create_regex` in Rofi's `helper.c`
+/// for a token (without the negation character), as `(method, token, regex)`.
+const PATTERNS: &[(MatchingMethod, &str, &str)] = &[
+    (MatchingMethod::Normal, "", ""),
+    (MatchingMethod::Normal, "foo", "foo"),
+    (MatchingMethod::Normal, "a.b*", "a\\.b\\*"),
+    (
+        MatchingMethod::Normal,
+        "\\|()[]{}^$*+?.",
+        "\\\\\\|\\(\\)\\[\\]\\{\\}\\^\\$\\*\\+\\?\\.",
+    ),
+    (MatchingMethod::Normal, "über-alles", "über-alles"),
+    (MatchingMethod::Regex, "", ""),
+    (MatchingMethod::Regex, "a.b*", "a.b*"),
+    (MatchingMethod::Regex, "^fo+$", "^fo+$"),
+    // Invalid regular expressions are only escaped when they fail to compile.
+    (MatchingMethod::Regex, "(c", "(c"),
+    (MatchingMethod::Glob, "", ""),
+    (MatchingMethod::Glob, "a?b*", "a\\Sb.*"),
+    (MatchingMethod::Glob, "a.b", "a\\.b"),
+    (MatchingMethod::Glob, "**", ".*.*"),
+    (MatchingMethod::Glob, "a\\*", "a\\\\.*"),
+    (MatchingMethod::Glob, "ü?", "ü\\S"),
+    (MatchingMethod::Fuzzy, "", ""),
+    (MatchingMethod::Fuzzy, "fb", "(f).*?(b)"),
+    (MatchingMethod::Fuzzy, "a.b", "(a).*?(\\.).*?(b)"),
+    (MatchingMethod::Fuzzy, "\\", "(\\\\)"),
+    (MatchingMethod::Fuzzy, "a\\", "(a).*?(\\\\)"),
+    (MatchingMethod::Fuzzy, "ü日", "(ü).*?(日)"),
+    (MatchingMethod::Prefix, "", "\\b"),
+    (MatchingMethod::Prefix, "foo", "\\bfoo"),
+    (MatchingMethod::Prefix, "a+b", "\\ba\\+b"),
+    (MatchingMethod::Prefix, "über", "\\büber"),
+];
+
+#[test]
+fn patterns() {
+    for &(method, token, regex) in PATTERNS {
+        assert_eq!(pattern(token, method), regex, "{} {token:?}", method.name());
+    }
+    for method in METHODS {
+        assert!(
+            PATTERNS.iter().any(|&(m, _, _)| m == method),
+            "no patterns for {}",
+            method.name(),
+        );
+    }
+}
+
+#[test]
+fn known_matches() {
+    let normal = config(MatchingMethod::Normal);
+    assert_eq!(filter("foo -bar", &normal), ["foo"]);
+    assert_eq!(filter("f.o", &normal), ["f.o"]);
+    assert_eq!(filter("über", &normal), ["Über", "über-alles"]);
+    assert_eq!(filter("", &normal), ENTRIES);
+
+    let case_sensitive = MatchingConfig {
+        case_sensitive: true,
+        ..normal.clone()
+    };
+    assert_eq!(filter("Foo", &case_sensitive), ["Foo Bar"]);
+    let case_smart = MatchingConfig {
+        case_smart: true,
+        ..normal.clone()
+    };
+    assert_eq!(filter("Foo", &case_smart), ["Foo Bar"]);
+    assert_eq!(filter("Bar o", &case_smart), ["Foo Bar"]);
+
+    let not_tokenized = MatchingConfig {
+        tokenize: false,
+        ..normal.clone()
+    };
+    assert_eq!(filter("bar f", &not_tokenized), ["bar foo"]);
+
+    assert_eq!(
+        filter("^foo", &config(MatchingMethod::Regex)),
+        ["foo", "Foo Bar", "foobar"]
+    );
+    assert_eq!(filter("(c", &config(MatchingMethod::Regex)), ["a+b (c)"]);
+    assert_eq!(
+        filter("f?o b*", &config(MatchingMethod::Glob)),
+        ["Foo Bar", "foobar", "bar foo", "barfoo"]
+    );
+    assert_eq!(
+        filter("fb", &config(MatchingMethod::Fuzzy)),
+        ["Foo Bar", "foobar"],
+    );
+    assert_eq!(
+        filter("foo", &config(MatchingMethod::Prefix)),
+        ["foo", "Foo Bar", "foobar", "bar foo"],
+    );
+}
+
+#[test]
+#[ignore = "needs a `rofi` binary that can connect to a display"]
+fn matches_rofi() {
+    let rofi = env::var("ROFI").unwrap_or_else(|_| "rofi".to_owned());
+    let input = ENTRIES[..ENTRIES.len() - 1].join("\n");
+
+    let mut mismatches = Vec::new();
+    for method in METHODS {
+        for &query in QUERIES {
+            let output = Command::new(&rofi)
+                .args(["-dmenu", "-dump", "-matching", method.name(), "-i"])
+                .args(["-filter", query])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .and_then(|mut child| {
+                    child.stdin.take().unwrap().write_all(input.as_bytes())?;
+                    child.wait_with_output()
+                });
+            let output = output.unwrap_or_else(|e| panic!("could not run `{rofi}`: {e}"));
+            assert!(
+                output.status.success(),
+                "`{rofi}` failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim(),
+            );
+            let rofi_matches: Vec<String> = String::from_utf8(output.stdout)
+                .unwrap()
+                .lines()
+                .map(str::to_owned)
+                .collect();
+            let our_matches: Vec<&str> = filter(query, &config(method))
+                .into_iter()
+                .filter(|entry| !entry.is_empty())
+                .collect();
+            if rofi_matches != our_matches {
+                mismatches.push(format!(
+                    "{} {query:?}: Rofi matched {rofi_matches:?}, we matched {our_matches:?}",
+                    method.name(),
+                ));
+            }
+        }
+    }
+    assert!(
+        mismatches.is_empty(),
+        "matching differs from Rofi:\n{}",
+        mismatches.join("\n"),
+    );
+}
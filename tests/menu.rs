@@ -0,0 +1,82 @@
+//! Decodes and encodes `menu_retv` values with `rofi_plugin_sys::MenuAction`.
+
+use {
+    rofi_plugin_sys::{menu, MenuAction, MenuFlags},
+    std::os::raw::c_int,
+};
+
+const ACTIONS: &[MenuAction] = &[
+    MenuAction::Ok { alt: false },
+    MenuAction::Ok { alt: true },
+    MenuAction::CustomInput { alt: false },
+    MenuAction::CustomInput { alt: true },
+    MenuAction::Delete,
+    MenuAction::Cancel,
+    MenuAction::Next,
+    MenuAction::Previous,
+    MenuAction::Complete,
+    MenuAction::QuickSwitch(0),
+    MenuAction::QuickSwitch(3),
+    MenuAction::QuickSwitch(u16::MAX),
+    MenuAction::CustomAction(1),
+    MenuAction::CustomAction(19),
+];
+
+#[test]
+fn round_trips() {
+    for &action in ACTIONS {
+        assert_eq!(MenuAction::from_retv(action.to_retv()), Some(action));
+        assert_eq!(c_int::from(action), action.to_retv());
+    }
+}
+
+#[test]
+fn decodes_rofi_values() {
+    assert_eq!(
+        MenuAction::from_retv(menu::OK | menu::CUSTOM_ACTION),
+        Some(MenuAction::Ok { alt: true }),
+    );
+    assert_eq!(
+        MenuAction::from_retv(menu::CUSTOM_COMMAND),
+        Some(MenuAction::CustomAction(1)),
+    );
+    assert_eq!(
+        MenuAction::from_retv(menu::QUICK_SWITCH | 2),
+        Some(MenuAction::QuickSwitch(2)),
+    );
+    assert_eq!(MenuAction::from_retv(0), None);
+    assert_eq!(MenuAction::from_retv(menu::LOWER_MASK), None);
+}
+
+#[test]
+fn rejects_out_of_range_custom_command() {
+    assert_eq!(
+        MenuAction::from_retv(menu::CUSTOM_COMMAND | 18),
+        Some(MenuAction::CustomAction(19)),
+    );
+    assert_eq!(MenuAction::from_retv(menu::CUSTOM_COMMAND | 19), None);
+    assert_eq!(
+        MenuAction::from_retv(menu::CUSTOM_COMMAND | menu::LOWER_MASK),
+        None,
+    );
+}
+
+#[test]
+#[should_panic]
+fn rejects_custom_action_zero() {
+    MenuAction::CustomAction(0).to_retv();
+}
+
+#[test]
+#[should_panic]
+fn rejects_custom_action_twenty() {
+    MenuAction::CustomAction(20).to_retv();
+}
+
+#[test]
+fn flags_ignore_data() {
+    assert_eq!(
+        MenuFlags::from_retv(menu::QUICK_SWITCH | menu::LOWER_MASK),
+        MenuFlags::QUICK_SWITCH,
+    );
+}
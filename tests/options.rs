@@ -0,0 +1,45 @@
+//! Registers options with `rofi_plugin_sys::options`
+//! and sets them through `rofi_plugin_sys::mock` as a configuration would.
+
+use rofi_plugin_sys::{mock, options};
+
+#[test]
+fn overrides_string_default() {
+    let option = options::add("test-path", Some("~/notes".to_owned()), "A path");
+    assert_eq!(option.key(), "test-path");
+    assert_eq!(option.get().as_deref(), Some("~/notes"));
+
+    assert!(mock::set_option("test-path", "/tmp/notes \n"));
+    assert_eq!(option.get().as_deref(), Some("/tmp/notes"));
+    assert!(mock::set_option("test-path", "/srv"));
+    assert_eq!(option.get().as_deref(), Some("/srv"));
+
+    assert!(!mock::set_option("test-missing", "/srv"));
+}
+
+#[test]
+fn overrides_unset_string() {
+    let option = options::add::<Option<String>>("test-unset", None, "An unset string");
+    assert_eq!(option.get(), None);
+    assert!(mock::set_option("test-unset", "set"));
+    assert_eq!(option.get().as_deref(), Some("set"));
+}
+
+#[test]
+fn parses_values() {
+    let number = options::add("test-number", 3_u32, "A number");
+    let signed = options::add("test-signed", -3_i32, "A signed number");
+    let boolean = options::add("test-bool", false, "A boolean");
+    let character = options::add("test-char", 'a', "A character");
+
+    assert!(mock::set_option("test-number", "42px"));
+    assert!(mock::set_option("test-signed", "-7"));
+    assert!(mock::set_option("test-bool", "TRUE"));
+    assert!(!mock::set_option("test-bool", "maybe"));
+    assert!(mock::set_option("test-char", "\\t"));
+
+    assert_eq!(number.get(), 42);
+    assert_eq!(signed.get(), -7);
+    assert!(boolean.get());
+    assert_eq!(character.get(), '\t');
+}
@@ -0,0 +1,61 @@
+//! Runs jobs on the thread pool created by `rofi_plugin_sys::mock`.
+
+use {
+    rofi_plugin_sys::{glib_sys, mock, pool},
+    std::{
+        ptr,
+        sync::{mpsc, Arc, Mutex},
+        thread,
+    },
+};
+
+#[test]
+fn spawns_jobs() {
+    mock::init_workers();
+    let (sender, receiver) = mpsc::channel();
+    for i in 0..8 {
+        let sender = sender.clone();
+        pool::spawn(glib_sys::G_PRIORITY_DEFAULT, move || {
+            sender.send((i, thread::current().id())).unwrap();
+        })
+        .unwrap();
+    }
+    drop(sender);
+    let mut results: Vec<_> = receiver.iter().collect();
+    results.sort_by_key(|&(i, _)| i);
+    assert_eq!(
+        results.iter().map(|&(i, _)| i).collect::<Vec<_>>(),
+        (0..8).collect::<Vec<_>>()
+    );
+    assert!(results.iter().all(|&(_, id)| id != thread::current().id()));
+}
+
+#[test]
+fn completes_on_main_context() {
+    mock::init_workers();
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let main_thread = thread::current().id();
+    for work in [Ok(21), Err("failed")] {
+        let results = Arc::clone(&results);
+        pool::spawn_with_completion(
+            glib_sys::G_PRIORITY_LOW,
+            move || match work {
+                Ok(n) => n * 2,
+                Err(message) => panic!("{}", message),
+            },
+            move |result| {
+                assert_eq!(thread::current().id(), main_thread);
+                let result = result.map_err(|payload| *payload.downcast::<String>().unwrap());
+                results.lock().unwrap().push(result);
+            },
+        )
+        .unwrap();
+    }
+
+    while results.lock().unwrap().len() < 2 {
+        unsafe { glib_sys::g_main_context_iteration(ptr::null_mut(), 1) };
+    }
+    let mut results = results.lock().unwrap().clone();
+    results.sort();
+    assert_eq!(results, [Ok(42), Err("failed".to_owned())]);
+}
@@ -0,0 +1,155 @@
+//! Reads hand-built properties with `rofi_plugin_sys::PropertyRef`,
+//! and their type names from the table defined by `rofi_plugin_sys::mock`.
+
+mod common;
+
+use {
+    common::{distance, leak_str, property, unit},
+    rofi_plugin_sys::{
+        glib_sys, Property, PropertyLink, PropertyRef, PropertyType, PropertyValue, RofiCursorType,
+        RofiOrientation, RofiPadding, RofiPixelUnit, WindowLocation,
+    },
+    std::{os::raw::c_char, ptr},
+};
+
+fn read(property: *mut Property) -> PropertyRef<'static> {
+    unsafe { PropertyRef::new(&*property) }
+}
+
+#[test]
+fn reads_scalars() {
+    let integer = property("a", PropertyType::Integer, PropertyValue { i: -4 });
+    assert!(matches!(read(integer), PropertyRef::Integer(-4)));
+
+    let double = property("a", PropertyType::Double, PropertyValue { f: 0.5 });
+    assert!(matches!(read(double), PropertyRef::Double(f) if f == 0.5));
+
+    let string = property(
+        "a",
+        PropertyType::String,
+        PropertyValue { s: leak_str("hi") },
+    );
+    assert!(matches!(read(string), PropertyRef::String(s) if s.to_bytes() == b"hi"));
+
+    let character = property("a", PropertyType::Char, PropertyValue { c: b'|' as c_char });
+    assert!(matches!(read(character), PropertyRef::Char(c) if c == b'|' as c_char));
+
+    let boolean = property("a", PropertyType::Boolean, PropertyValue { b: 1 });
+    assert!(matches!(read(boolean), PropertyRef::Boolean(true)));
+}
+
+#[test]
+fn reads_enums() {
+    let position = property(
+        "a",
+        PropertyType::Position,
+        PropertyValue {
+            i: WindowLocation::SOUTH_EAST.bits() as i32,
+        },
+    );
+    assert!(matches!(
+        read(position),
+        PropertyRef::Position(WindowLocation::SOUTH_EAST),
+    ));
+
+    let orientation = property("a", PropertyType::Orientation, PropertyValue { i: 1 });
+    assert!(matches!(
+        read(orientation),
+        PropertyRef::Orientation(RofiOrientation::Horizontal),
+    ));
+
+    let cursor = property("a", PropertyType::Cursor, PropertyValue { i: 2 });
+    assert!(matches!(
+        read(cursor),
+        PropertyRef::Cursor(RofiCursorType::Text),
+    ));
+
+    let inherit = property("a", PropertyType::Inherit, PropertyValue { i: 0 });
+    assert!(matches!(read(inherit), PropertyRef::Inherit));
+}
+
+#[test]
+fn reads_lists_and_links() {
+    let items = [1, 2, 3].map(|i| property("", PropertyType::Integer, PropertyValue { i }));
+    let list = items.iter().fold(ptr::null_mut(), |list, &item| unsafe {
+        glib_sys::g_list_append(list, item.cast())
+    });
+    let list = property("a", PropertyType::List, PropertyValue { list });
+    match read(list) {
+        PropertyRef::List(items) => {
+            let items: Vec<i32> = items
+                .map(|item| match unsafe { PropertyRef::new(item) } {
+                    PropertyRef::Integer(i) => i,
+                    other => panic!("unexpected item {other:?}"),
+                })
+                .collect();
+            assert_eq!(items, [1, 2, 3]);
+        }
+        other => panic!("unexpected value {other:?}"),
+    }
+
+    let default = property("", PropertyType::Integer, PropertyValue { i: 8 });
+    let link = property(
+        "a",
+        PropertyType::Link,
+        PropertyValue {
+            link: PropertyLink {
+                name: leak_str("gap"),
+                property_ref: ptr::null_mut(),
+                def_value: default,
+            },
+        },
+    );
+    match read(link) {
+        PropertyRef::Link {
+            name,
+            resolved: None,
+            default: Some(default),
+        } => {
+            assert_eq!(name.to_bytes(), b"gap");
+            assert!(matches!(
+                unsafe { PropertyRef::new(default) },
+                PropertyRef::Integer(8),
+            ));
+        }
+        other => panic!("unexpected value {other:?}"),
+    }
+}
+
+#[test]
+fn reads_padding() {
+    let padding = property(
+        "a",
+        PropertyType::Padding,
+        PropertyValue {
+            padding: RofiPadding {
+                top: distance(unit(1.0, RofiPixelUnit::Px)),
+                right: distance(unit(2.0, RofiPixelUnit::Em)),
+                bottom: distance(unit(3.0, RofiPixelUnit::Px)),
+                left: distance(unit(4.0, RofiPixelUnit::Percent)),
+            },
+        },
+    );
+    match read(padding) {
+        PropertyRef::Padding(padding) => {
+            assert_eq!(padding.right.base.distance, 2.0);
+            assert_eq!(padding.right.base.unit_type, RofiPixelUnit::Em);
+            assert_eq!(padding.left.base.distance, 4.0);
+        }
+        other => panic!("unexpected value {other:?}"),
+    }
+}
+
+#[test]
+fn names_types() {
+    let integer = read(property("a", PropertyType::Integer, PropertyValue { i: 0 }));
+    assert_eq!(integer.ty(), PropertyType::Integer);
+    assert_eq!(
+        integer.ty().name().map(|name| name.to_bytes()),
+        Some(&b"Integer"[..]),
+    );
+    assert_eq!(
+        PropertyType::Inherit.name().map(|name| name.to_bytes()),
+        Some(&b"Inherit"[..]),
+    );
+}
@@ -0,0 +1,171 @@
+//! Checks `rofi_plugin_sys::rasi` against theme sections built by hand,
+//! laid out like Rofi's theme parser lays them out.
+
+mod common;
+
+use {
+    common::{distance, leak_str, op, property, unit, widget},
+    rofi_plugin_sys::{
+        glib_sys, rasi, PropertyLink, PropertyType, PropertyValue, RofiDistance,
+        RofiDistanceModifier, RofiHighlightColorStyle, RofiHighlightStyle, RofiLineStyle,
+        RofiPadding, RofiPixelUnit, ThemeColor, ThemeMedia, ThemeMediaType, WindowLocation,
+    },
+    std::ptr,
+};
+
+#[test]
+fn formats_distances() {
+    let px = distance(unit(4.0, RofiPixelUnit::Px));
+    assert_eq!(unsafe { rasi::distance(&px) }.to_string(), "4px");
+
+    let dashed = RofiDistance {
+        style: RofiLineStyle::Dash,
+        ..distance(unit(1.5, RofiPixelUnit::Em))
+    };
+    assert_eq!(unsafe { rasi::distance(&dashed) }.to_string(), "1.5em dash");
+
+    // calc((100% - 2em) / 2px), with the groups the parser creates
+    let calc = distance(op(
+        RofiDistanceModifier::Group,
+        op(
+            RofiDistanceModifier::Divide,
+            op(
+                RofiDistanceModifier::Group,
+                op(
+                    RofiDistanceModifier::Subtract,
+                    unit(100.0, RofiPixelUnit::Percent),
+                    Some(unit(2.0, RofiPixelUnit::Em)),
+                ),
+                None,
+            ),
+            Some(unit(2.0, RofiPixelUnit::Px)),
+        ),
+        None,
+    ));
+    assert_eq!(
+        unsafe { rasi::distance(&calc) }.to_string(),
+        "calc((100% - 2em) / 2px)"
+    );
+
+    let padding = |top, right, bottom, left| RofiPadding {
+        top: distance(unit(top, RofiPixelUnit::Px)),
+        right: distance(unit(right, RofiPixelUnit::Px)),
+        bottom: distance(unit(bottom, RofiPixelUnit::Px)),
+        left: distance(unit(left, RofiPixelUnit::Px)),
+    };
+    let format = |p: RofiPadding| unsafe { rasi::padding(&p) }.to_string();
+    assert_eq!(format(padding(1.0, 1.0, 1.0, 1.0)), "1px");
+    assert_eq!(format(padding(1.0, 2.0, 1.0, 2.0)), "1px 2px");
+    assert_eq!(format(padding(1.0, 2.0, 3.0, 2.0)), "1px 2px 3px");
+    assert_eq!(format(padding(1.0, 2.0, 3.0, 4.0)), "1px 2px 3px 4px");
+}
+
+#[test]
+fn formats_widget_tree() {
+    let red = ThemeColor {
+        red: 1.0,
+        green: 0.0,
+        blue: 0.0,
+        alpha: 0.5,
+    };
+    let default = property("", PropertyType::Integer, PropertyValue { i: 8 });
+    let list = [
+        property("", PropertyType::String, PropertyValue { s: leak_str("a") }),
+        property(
+            "",
+            PropertyType::String,
+            PropertyValue {
+                s: leak_str("say \"hi\""),
+            },
+        ),
+    ]
+    .iter()
+    .fold(ptr::null_mut(), |list, &item| unsafe {
+        glib_sys::g_list_append(list, item.cast())
+    });
+
+    let root = widget("Root", ptr::null_mut(), None, &[]);
+    let plugin = widget(
+        "my-plugin",
+        root,
+        None,
+        &[
+            property("ratio", PropertyType::Double, PropertyValue { f: 1.0 }),
+            property(
+                "anchor",
+                PropertyType::Position,
+                PropertyValue {
+                    i: WindowLocation::SOUTH_EAST.bits() as i32,
+                },
+            ),
+            property("items", PropertyType::List, PropertyValue { list }),
+            property(
+                "spacing",
+                PropertyType::Link,
+                PropertyValue {
+                    link: PropertyLink {
+                        name: leak_str("gap"),
+                        property_ref: ptr::null_mut(),
+                        def_value: default,
+                    },
+                },
+            ),
+        ],
+    );
+    let selected = widget("selected", plugin, None, &[]);
+    widget(
+        "normal",
+        selected,
+        None,
+        &[property(
+            "highlight",
+            PropertyType::Highlight,
+            PropertyValue {
+                highlight: RofiHighlightColorStyle {
+                    style: RofiHighlightStyle::BOLD | RofiHighlightStyle::COLOR,
+                    color: red,
+                },
+            },
+        )],
+    );
+    let media = widget(
+        "@media ( max-width: 800 )",
+        root,
+        Some(ThemeMedia {
+            r#type: ThemeMediaType::MaxWidth,
+            value: 800.0,
+            boolv: 0,
+        }),
+        &[],
+    );
+    widget(
+        "my-plugin",
+        media,
+        None,
+        &[property(
+            "show",
+            PropertyType::Boolean,
+            PropertyValue { b: 0 },
+        )],
+    );
+
+    assert_eq!(
+        unsafe { rasi::widget(&*root) }.to_string(),
+        "\
+my-plugin {
+    anchor: south east;
+    items: [ \"a\", \"say \\\"hi\\\"\" ];
+    ratio: 1.0;
+    spacing: var(gap, 8);
+}
+my-plugin selected.normal {
+    highlight: bold rgba(255, 0, 0, 50%);
+}
+@media ( max-width: 800 ) {
+    my-plugin {
+        show: false;
+    }
+}
+"
+    );
+}
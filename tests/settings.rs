@@ -0,0 +1,64 @@
+//! Reads the default configuration defined by `rofi_plugin_sys::mock`
+//! through `rofi_plugin_sys::settings`.
+
+use {
+    rofi_plugin_sys::{
+        matching,
+        settings::{self, MatchingMethod, SortingMethod},
+        sorting,
+    },
+    std::ptr,
+};
+
+// A single test, since the configuration is shared by all threads.
+#[test]
+fn snapshots_config() {
+    reads_defaults();
+    reads_changes();
+}
+
+fn reads_defaults() {
+    let settings = unsafe { settings::snapshot() };
+    assert_eq!(settings.modes.as_deref(), Some("window,run,ssh"));
+    assert!(!settings.show_icons);
+    assert_eq!(settings.icon_theme, None);
+    assert_eq!(settings.terminal.as_deref(), Some("rofi-sensible-terminal"));
+    assert_eq!(settings.run_command.as_deref(), Some("{cmd}"));
+    assert_eq!(
+        settings.run_shell_command.as_deref(),
+        Some("{terminal} -e {cmd}"),
+    );
+    assert_eq!(settings.matching.method, matching::MatchingMethod::Normal);
+    assert!(settings.matching.tokenize);
+    assert_eq!(settings.matching.negate_char, Some('-'));
+    assert!(!settings.matching.case_sensitive);
+    assert!(!settings.sort);
+    assert_eq!(settings.sorting_method, sorting::SortingMethod::Normal);
+    assert_eq!(settings.threads, 0);
+    assert_eq!(settings.cache_dir, None);
+}
+
+fn reads_changes() {
+    let original = unsafe { *ptr::addr_of!(settings::config) };
+    unsafe {
+        settings::config.show_icons = 1;
+        settings::config.matching_method = MatchingMethod::Fuzzy;
+        settings::config.matching_negate_char = 0;
+        settings::config.case_sensitive = 1;
+        settings::config.sort = 1;
+        settings::config.sorting_method_enum = SortingMethod::Fzf;
+        settings::config.threads = 1;
+    }
+
+    let settings = unsafe { settings::snapshot() };
+    unsafe { settings::config = original };
+
+    assert!(settings.show_icons);
+    assert_eq!(settings.matching.method, matching::MatchingMethod::Fuzzy);
+    assert_eq!(settings.matching.negate_char, None);
+    assert!(settings.matching.case_sensitive);
+    assert!(settings.matching.is_case_sensitive("input"));
+    assert!(settings.sort);
+    assert_eq!(settings.sorting_method, sorting::SortingMethod::Fzf);
+    assert_eq!(settings.threads, 1);
+}
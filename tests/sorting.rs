@@ -0,0 +1,109 @@
+//! Checks the scoring functions of `rofi_plugin_sys::sorting` against known values,
+//! worked out by hand from Rofi's C implementation,
+//! and the `helper` functions that wrap them in `rofi_plugin_sys::mock`.
+
+use {
+    rofi_plugin_sys::{
+        helper,
+        sorting::{fuzzy_score, levenshtein, rank, SortingMethod},
+    },
+    std::os::raw::c_int,
+};
+
+/// The score of a string that the pattern does not match at all.
+const NO_MATCH: c_int = -(c_int::MIN / 2);
+
+#[test]
+fn levenshtein_distances() {
+    assert_eq!(levenshtein("", "", false), 0);
+    assert_eq!(levenshtein("", "abc", false), 3);
+    assert_eq!(levenshtein("abc", "", false), 3);
+    assert_eq!(levenshtein("kitten", "sitting", false), 3);
+    assert_eq!(levenshtein("flaw", "lawn", false), 2);
+
+    assert_eq!(levenshtein("ABC", "abc", false), 0);
+    assert_eq!(levenshtein("ABC", "abc", true), 3);
+    assert_eq!(levenshtein("aBc", "abc", true), 1);
+
+    // Distances are counted in characters, not bytes.
+    assert_eq!(levenshtein("naïve", "naive", false), 1);
+    assert_eq!(levenshtein("ÜBER", "über", false), 0);
+    assert_eq!(levenshtein("Über", "über", true), 1);
+    assert_eq!(levenshtein("日本", "日本語", false), 1);
+}
+
+#[test]
+fn fuzzy_scores() {
+    // A match at the start of a word, doubled for the start of the pattern.
+    assert_eq!(fuzzy_score("a", "a", false), -100);
+    // A leading gap of one character.
+    assert_eq!(fuzzy_score("a", "ba", false), 4);
+    // A word start followed by a consecutive match.
+    assert_eq!(fuzzy_score("ab", "ab", false), -145);
+    // A camel-case boundary scores better than the middle of a word.
+    assert!(fuzzy_score("b", "aB", false) < fuzzy_score("b", "ab", false));
+
+    assert_eq!(fuzzy_score("AB", "ab", false), -145);
+    assert_eq!(fuzzy_score("AB", "ab", true), NO_MATCH);
+
+    assert_eq!(fuzzy_score("é", "é", false), -100);
+    assert_eq!(fuzzy_score("É", "é", false), -100);
+    assert_eq!(fuzzy_score("é", "aé", false), 4);
+
+    assert_eq!(fuzzy_score("a", "", false), NO_MATCH);
+    assert_eq!(fuzzy_score("", "abc", false), NO_MATCH);
+    assert_eq!(fuzzy_score("x", "abc", false), NO_MATCH);
+    assert_eq!(fuzzy_score("a", &"a".repeat(257), false), NO_MATCH);
+}
+
+#[test]
+fn ranks_entries() {
+    let entries = ["abcd", "ab", "abc"];
+    assert_eq!(
+        rank(&entries, "ab", SortingMethod::Normal, false),
+        [1, 2, 0]
+    );
+    assert_eq!(rank(&entries, "AB", SortingMethod::Normal, true), [1, 2, 0]);
+
+    let entries = ["Settings", "set-theme", "reset"];
+    assert_eq!(rank(&entries, "st", SortingMethod::Fzf, false), [1, 0, 2]);
+
+    // Entries with the same distance keep their order.
+    let entries = ["b", "a", "c"];
+    assert_eq!(rank(&entries, "", SortingMethod::Normal, false), [0, 1, 2]);
+    assert_eq!(rank(&entries, "x", SortingMethod::Fzf, false), [0, 1, 2]);
+    assert_eq!(
+        rank(&[] as &[&str], "x", SortingMethod::Fzf, false),
+        Vec::<usize>::new()
+    );
+}
+
+/// The mock's `levenshtein` and `rofi_scorer_fuzzy_evaluate` are implemented with
+/// `sorting::levenshtein` and `sorting::fuzzy_score`,
+/// so this only checks that the helpers pass strings and their lengths in characters correctly.
+#[test]
+fn helpers_pass_lengths() {
+    assert_eq!(helper::levenshtein_str("", "", false), 0);
+    assert_eq!(helper::levenshtein_str("kitten", "sitting", false), 3);
+    assert_eq!(helper::levenshtein_str("naïve", "naive", false), 1);
+    assert_eq!(helper::levenshtein_str("日本", "日本語", false), 1);
+    assert_eq!(helper::levenshtein_str("Über", "über", true), 1);
+
+    assert_eq!(helper::scorer_fuzzy_evaluate_str("ab", "ab", false), -145);
+    assert_eq!(helper::scorer_fuzzy_evaluate_str("É", "é", false), -100);
+    assert_eq!(helper::scorer_fuzzy_evaluate_str("é", "aé", false), 4);
+    assert_eq!(
+        helper::scorer_fuzzy_evaluate_str("AB", "ab", true),
+        NO_MATCH
+    );
+
+    // The helpers must not read past the end of a string that is not NUL-terminated.
+    let text = "abcdef";
+    assert_eq!(helper::levenshtein_str(&text[..3], &text[3..], false), 3);
+    assert_eq!(helper::levenshtein_str(&text[..2], &text[..3], false), 1);
+    let text = "éaé";
+    assert_eq!(
+        helper::scorer_fuzzy_evaluate_str(&text[..2], &text[2..], false),
+        4
+    );
+}
@@ -0,0 +1,82 @@
+//! Runs `rofi_plugin_sys::timer` on the default main context,
+//! using the reloads recorded by `rofi_plugin_sys::mock`.
+
+use {
+    rofi_plugin_sys::{glib_sys, mock, mode_set_private_data, timer::Timer, Mode},
+    std::{
+        ptr,
+        time::{Duration, Instant},
+    },
+};
+
+struct State {
+    ticks: u32,
+    timer: Option<Timer>,
+}
+
+/// Run the main context until `done` returns true.
+fn run_until(mut done: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !done() {
+        assert!(Instant::now() < deadline, "timed out");
+        unsafe { glib_sys::g_main_context_iteration(ptr::null_mut(), 1) };
+    }
+}
+
+// A single test, so that the timers always run on the thread that checks them.
+#[test]
+fn refreshes_on_main_context() {
+    refreshes_until_destroyed();
+    stops_without_private_data();
+}
+
+fn refreshes_until_destroyed() {
+    let mut mode = Mode::default();
+    let state = Box::into_raw(Box::new(State {
+        ticks: 0,
+        timer: None,
+    }));
+    unsafe { mode_set_private_data(&mut mode, state.cast()) };
+    let timer = unsafe {
+        Timer::start(&mode, Duration::from_millis(1), |state: &mut State| {
+            state.ticks += 1;
+            // Only report a change on every other tick.
+            state.ticks % 2 == 0
+        })
+    };
+    unsafe { (*state).timer = Some(timer) };
+
+    run_until(|| unsafe { (*state).ticks } == 4);
+    assert_eq!(mock::take_calls(), [mock::Call::Reload, mock::Call::Reload]);
+
+    // What a mode's `_destroy` does.
+    unsafe { mode_set_private_data(&mut mode, ptr::null_mut()) };
+    drop(unsafe { Box::from_raw(state) });
+
+    let mut stopped = false;
+    let source = unsafe {
+        glib_sys::g_timeout_add_full(
+            glib_sys::G_PRIORITY_DEFAULT,
+            20,
+            Some(set_flag),
+            (&mut stopped as *mut bool).cast(),
+            None,
+        )
+    };
+    assert_ne!(source, 0);
+    run_until(|| stopped);
+    assert_eq!(mock::take_calls(), []);
+}
+
+fn stops_without_private_data() {
+    let mode = Mode::default();
+    let timer = unsafe { Timer::start(&mode, Duration::from_millis(1), |_: &mut State| true) };
+    run_until(|| !timer.is_active());
+    drop(timer);
+    assert_eq!(mock::take_calls(), []);
+}
+
+unsafe extern "C" fn set_flag(data: glib_sys::gpointer) -> glib_sys::gboolean {
+    unsafe { *data.cast::<bool>() = true };
+    glib_sys::G_SOURCE_REMOVE
+}
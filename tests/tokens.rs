@@ -0,0 +1,95 @@
+//! Tokenizes and matches input with `rofi_plugin_sys::helper::Tokens`,
+//! using the matchers provided by `rofi_plugin_sys::mock`.
+
+use {
+    rofi_plugin_sys::{
+        helper::Tokens,
+        matching::{MatchingConfig, MatchingMethod},
+        mock,
+    },
+    std::ffi::CString,
+};
+
+#[test]
+fn empty_input() {
+    for input in ["", " ", "   "] {
+        let tokens = Tokens::new(input, false);
+        assert!(tokens.is_empty());
+        assert_eq!(tokens.len(), 0);
+        assert_eq!(tokens.iter().next(), None);
+        assert!(tokens.matches(""));
+        assert!(tokens.matches("anything"));
+    }
+}
+
+#[test]
+fn splits_tokens() {
+    let tokens = Tokens::new("foo -bar", false);
+    assert!(!tokens.is_empty());
+    assert_eq!(tokens.len(), 2);
+    let inverted: Vec<bool> = tokens.iter().map(|token| token.invert != 0).collect();
+    assert_eq!(inverted, [false, true]);
+    assert_eq!((&tokens).into_iter().count(), 2);
+
+    assert!(tokens.matches("food"));
+    assert!(tokens.matches("a foo"));
+    assert!(!tokens.matches("foobar"));
+    assert!(!tokens.matches("baz"));
+    assert!(!tokens.matches(""));
+
+    // Tokens match anywhere and in any order.
+    let tokens = Tokens::new("b a", false);
+    assert!(tokens.matches("ab"));
+    assert!(!tokens.matches("a"));
+}
+
+#[test]
+fn without_tokenize() {
+    mock::set_matching(MatchingConfig {
+        tokenize: false,
+        ..MatchingConfig::default()
+    });
+    let tokens = Tokens::new("foo bar", false);
+    assert_eq!(tokens.len(), 1);
+    assert!(tokens.matches("foo bar"));
+    assert!(!tokens.matches("bar foo"));
+}
+
+#[test]
+fn case_sensitivity() {
+    let tokens = Tokens::new("Foo", false);
+    assert!(tokens.matches("foo"));
+    assert!(tokens.matches("FOO"));
+
+    let tokens = Tokens::new("Foo", true);
+    assert!(tokens.matches("Foo"));
+    assert!(!tokens.matches("foo"));
+    assert!(!tokens.matches("FOO"));
+}
+
+#[test]
+fn multibyte() {
+    let tokens = Tokens::new("ÜBER", false);
+    assert!(tokens.matches("über alles"));
+    assert!(!Tokens::new("ÜBER", true).matches("über alles"));
+
+    let tokens = Tokens::new("日本", false);
+    assert!(tokens.matches("日本語"));
+    assert!(!tokens.matches("本日"));
+
+    mock::set_matching(MatchingConfig {
+        method: MatchingMethod::Fuzzy,
+        ..MatchingConfig::default()
+    });
+    let tokens = Tokens::new("né", false);
+    assert!(tokens.matches("naïve café"));
+    assert!(!tokens.matches("café naïve"));
+}
+
+#[test]
+fn truncates_at_nul() {
+    let tokens = Tokens::new("bar", false);
+    assert!(!tokens.matches("foo\0bar"));
+    assert!(tokens.matches("bar\0foo"));
+    assert!(tokens.matches_c_str(&CString::new("foobar").unwrap()));
+}
@@ -0,0 +1,68 @@
+//! Checks that `rofi_plugin_sys::view` schedules reloads on the main context,
+//! using the reloads recorded by `rofi_plugin_sys::mock`.
+
+use {
+    rofi_plugin_sys::{
+        glib_sys, mock,
+        view::{EntryBuffer, ReloadHandle},
+    },
+    std::{ptr, thread},
+};
+
+fn dispatch_pending() {
+    while unsafe { glib_sys::g_main_context_iteration(ptr::null_mut(), 0) } != 0 {}
+}
+
+// A single test, so that the reloads are always dispatched on the thread that checks them.
+#[test]
+fn reloads_on_main_context() {
+    coalesces_reloads();
+    streams_entries();
+}
+
+fn coalesces_reloads() {
+    let reload = ReloadHandle::new();
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let reload = reload.clone();
+            thread::spawn(move || reload.request())
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    reload.request();
+    assert!(reload.is_pending());
+    assert_eq!(mock::take_calls(), []);
+
+    dispatch_pending();
+    assert!(!reload.is_pending());
+    assert_eq!(mock::take_calls(), [mock::Call::Reload]);
+
+    reload.request();
+    dispatch_pending();
+    assert_eq!(mock::take_calls(), [mock::Call::Reload]);
+}
+
+fn streams_entries() {
+    let buffer = EntryBuffer::new();
+    let producer = {
+        let buffer = buffer.clone();
+        thread::spawn(move || {
+            for i in 0..100 {
+                buffer.push(i);
+            }
+            buffer.extend(Vec::new());
+        })
+    };
+    producer.join().unwrap();
+    assert!(buffer.reload_handle().is_pending());
+
+    dispatch_pending();
+    assert_eq!(mock::take_calls(), [mock::Call::Reload]);
+    assert_eq!(buffer.take(), (0..100).collect::<Vec<_>>());
+    assert_eq!(buffer.take(), []);
+
+    buffer.extend(Vec::new());
+    assert!(!buffer.reload_handle().is_pending());
+}
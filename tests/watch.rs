@@ -0,0 +1,160 @@
+//! Watches a temporary directory with `rofi_plugin_sys::watch`,
+//! using the reloads recorded by `rofi_plugin_sys::mock`.
+
+use {
+    rofi_plugin_sys::{glib_sys, mock, mode_set_private_data, watch::FileWatcher, Mode},
+    std::{
+        env, fs,
+        path::PathBuf,
+        process, ptr,
+        time::{Duration, Instant},
+    },
+};
+
+struct State {
+    dir: PathBuf,
+    files: Vec<String>,
+    loads: u32,
+}
+
+impl State {
+    fn load(&mut self) {
+        let mut files: Vec<String> = fs::read_dir(&self.dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        self.files = files;
+        self.loads += 1;
+    }
+}
+
+/// Run the main context until `done` returns true, or until `timeout` has passed.
+fn run_until(timeout: Duration, mut done: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    while !done() {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        unsafe { glib_sys::g_main_context_iteration(ptr::null_mut(), 0) };
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    true
+}
+
+// A single test, so that the reloads are always dispatched on the thread that checks them.
+#[test]
+fn reloads_on_main_context() {
+    reloads_on_changes();
+    loader_drops_watcher();
+}
+
+fn reloads_on_changes() {
+    let dir = env::temp_dir().join(format!("rofi-plugin-sys-watch-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut mode = Mode::default();
+    let state = Box::into_raw(Box::new(State {
+        dir: dir.clone(),
+        files: Vec::new(),
+        loads: 0,
+    }));
+    unsafe { mode_set_private_data(&mut mode, state.cast()) };
+    let watcher = unsafe {
+        FileWatcher::start(
+            &mode,
+            [&dir],
+            Duration::from_millis(50),
+            |state: &mut State| {
+                state.load();
+                true
+            },
+        )
+    }
+    .unwrap();
+
+    // A burst of changes is debounced into a single load.
+    for name in ["a", "b", "c"] {
+        fs::write(dir.join(name), name).unwrap();
+    }
+    assert!(run_until(Duration::from_secs(5), || unsafe {
+        (*state).loads
+    } == 1));
+    assert_eq!(unsafe { &(*state).files }, &["a", "b", "c"]);
+    assert_eq!(mock::take_calls(), [mock::Call::Reload]);
+
+    fs::remove_file(dir.join("b")).unwrap();
+    assert!(run_until(Duration::from_secs(5), || unsafe {
+        (*state).loads
+    } == 2));
+    assert_eq!(unsafe { &(*state).files }, &["a", "c"]);
+    assert_eq!(mock::take_calls(), [mock::Call::Reload]);
+
+    // No more loads once the watcher is dropped.
+    drop(watcher);
+    fs::write(dir.join("d"), "d").unwrap();
+    assert!(!run_until(Duration::from_millis(200), || unsafe {
+        (*state).loads
+    } > 2));
+    assert_eq!(mock::take_calls(), []);
+
+    unsafe { mode_set_private_data(&mut mode, ptr::null_mut()) };
+    drop(unsafe { Box::from_raw(state) });
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+struct OwningState {
+    watcher: Option<FileWatcher>,
+    loads: u32,
+}
+
+fn loader_drops_watcher() {
+    let dir = env::temp_dir().join(format!("rofi-plugin-sys-watch-drop-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut mode = Mode::default();
+    let state = Box::into_raw(Box::new(OwningState {
+        watcher: None,
+        loads: 0,
+    }));
+    unsafe { mode_set_private_data(&mut mode, state.cast()) };
+    let watcher = unsafe {
+        FileWatcher::start(
+            &mode,
+            [&dir],
+            Duration::from_millis(50),
+            |state: &mut OwningState| {
+                state.watcher = None;
+                state.loads += 1;
+                true
+            },
+        )
+    }
+    .unwrap();
+    unsafe { (*state).watcher = Some(watcher) };
+
+    fs::write(dir.join("a"), "a").unwrap();
+    assert!(run_until(Duration::from_secs(5), || unsafe {
+        (*state).loads
+    } == 1));
+    assert!(unsafe { (*state).watcher.is_none() });
+    assert_eq!(mock::take_calls(), [mock::Call::Reload]);
+
+    fs::write(dir.join("b"), "b").unwrap();
+    assert!(!run_until(Duration::from_millis(200), || unsafe {
+        (*state).loads
+    } > 1));
+
+    unsafe { mode_set_private_data(&mut mode, ptr::null_mut()) };
+    drop(unsafe { Box::from_raw(state) });
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reports_invalid_paths() {
+    let mode = Mode::default();
+    let error =
+        unsafe { FileWatcher::start(&mode, ["nul\0byte"], Duration::ZERO, |_: &mut ()| true) }
+            .unwrap_err();
+    assert_eq!(error.path().to_str(), Some("nul\0byte"));
+}